@@ -0,0 +1,7 @@
+// Sets up `node --test` to run the TypeScript tests in src/ (see `npm test`)
+import { register } from "node:module";
+
+register("./ts-loader.mjs", import.meta.url);
+
+// The solver module listens for worker messages on `self` as soon as it's imported
+globalThis.self ??= {addEventListener() {}, postMessage() {}};
@@ -0,0 +1,42 @@
+// Module hooks that let Node import the TypeScript sources the way Vite does: extensionless
+// relative imports, `?raw` text imports, and `import.meta.env`
+import { existsSync } from "node:fs";
+import { readFile } from "node:fs/promises";
+import { fileURLToPath } from "node:url";
+import { transform } from "esbuild";
+
+export async function resolve(specifier, context, next) {
+    if (specifier.endsWith("?raw")) {
+        return {url: new URL(specifier, context.parentURL).href, shortCircuit: true};
+    }
+    if (specifier.startsWith(".") && !/\.[cm]?[jt]sx?$/.test(specifier)) {
+        for (const extension of [".ts", ".tsx"]) {
+            const url = new URL(specifier + extension, context.parentURL);
+            if (existsSync(fileURLToPath(url))) {
+                return {url: url.href, shortCircuit: true};
+            }
+        }
+    }
+    return next(specifier, context);
+}
+
+export async function load(url, context, next) {
+    if (url.endsWith("?raw")) {
+        const text = await readFile(fileURLToPath(url.slice(0, -"?raw".length)), "utf8");
+        return {format: "module", source: "export default " + JSON.stringify(text) + ";", shortCircuit: true};
+    }
+    if (url.endsWith(".ts")) {
+        const source = await readFile(fileURLToPath(url), "utf8");
+        const result = await transform(source, {
+            loader: "ts",
+            format: "esm",
+            target: "es2020",
+            sourcefile: fileURLToPath(url),
+            sourcemap: "inline",
+            // Build with both dictionaries, as when VITE_DICTIONARIES isn't set
+            define: {"import.meta.env.VITE_DICTIONARIES": "undefined"}
+        });
+        return {format: "module", source: result.code, shortCircuit: true};
+    }
+    return next(url, context);
+}
@@ -4,18 +4,33 @@ import "primereact/resources/primereact.min.css";
 import 'primeicons/primeicons.css';
 import { Splitter, SplitterPanel } from "primereact/splitter";
 import { Toast } from "primereact/toast";
+import { confirmDialog } from "primereact/confirmdialog";
 import "./App.css";
 import LetterInput from "./letter_input";
 import ResultsDisplay from "./results_display";
 import PlayableWords from "./playable_words";
-import { AppState, convert_word_to_array } from "./solver";
+import BestBoardPreview from "./best_board_preview";
+import { AppState, BestBoard, SearchState, SearchTimeout, SolverConfig, best_board_solution, convert_words_to_arena, dictionary_capabilities, hash_dictionary, init_dictionaries, rank_commonness, record_word_history } from "./solver";
 import { result_t } from "./types";
 
+/**
+ * Options passed to the solver
+ */
+const SOLVER_CONFIG: SolverConfig = {
+    // Check in with the user periodically during long solves rather than running indefinitely
+    max_words_to_check: 50000,
+    // Show how far long solves have gotten, so the user can stop early
+    stream_best_boards: true
+};
+
 export default function App() {
     const toast = useRef<Toast>(null);
     const [gameState, setGameState] = useState<AppState|null>(null);
     const [running, setRunning] = useState(false);
     const [results, setResults] = useState<result_t|null>(null);
+    const [bestBoard, setBestBoard] = useState<BestBoard|null>(null);
+    const worker = useRef<Worker|null>(null);
+    const solveStart = useRef(new Date());
     const [letterInputContextMenu, setLetterInputContextMenu] = useState<MouseEvent<HTMLDivElement>|null>(null);
     const [resultsContextMenu, setResultsContextMenu] = useState<MouseEvent<HTMLDivElement>|null>(null);
     const [playableWordsVisible, setPlayableWordsVisible] = useState(false);
@@ -25,18 +40,35 @@ export default function App() {
     // Disable right-clicking elsewhere on the page and load the data
     useEffect(() => {
         //document.addEventListener("contextmenu", e => e.preventDefault());
+        // Only download the dictionaries this build includes
+        const capabilities = dictionary_capabilities();
+        /**
+         * Downloads a dictionary if it's in this build
+         * @param available Whether the dictionary is in this build
+         * @param url Where to download it from
+         * @returns The response, or `null` if the dictionary isn't in this build
+         */
+        const fetchIf = (available: boolean, url: string) => available ? fetch(url) : Promise.resolve(null);
         Promise.all([
-            fetch("https://raw.githubusercontent.com/williamdwatson/bananagrams_solver/main/src-tauri/src/dictionary.txt"),
-            fetch("https://raw.githubusercontent.com/williamdwatson/bananagrams_solver/main/src-tauri/src/short_dictionary.txt")
+            fetchIf(capabilities.long, "https://raw.githubusercontent.com/williamdwatson/bananagrams_solver/main/src-tauri/src/dictionary.txt"),
+            fetchIf(capabilities.short, "https://raw.githubusercontent.com/williamdwatson/bananagrams_solver/main/src-tauri/src/short_dictionary.txt")
         ]).then(([long, short]) => {
             Promise.all([
-                long.text(), short.text()
+                long?.text() ?? "", short?.text() ?? ""
             ]).then(([long_text, short_text]) => {
-                setGameState({
+                const all_words_long = convert_words_to_arena(long_text.split("\n").filter(word => word.length > 1).map(word => word.toUpperCase().trim()));
+                // Without the common words, the solver uses the full dictionary instead
+                const all_words_short = capabilities.short ? convert_words_to_arena(short_text.split("\n").filter(word => word.length > 1).map(word => word.toUpperCase().trim())) : all_words_long;
+                const state: AppState = {
                     last_game: null,
-                    all_words_long: long_text.split("\n").filter(word => word.length > 1).map(word => convert_word_to_array(word.toUpperCase().trim())),
-                    all_words_short: short_text.split("\n").filter(word => word.length > 1).map(word => convert_word_to_array(word.toUpperCase().trim()))
-                });
+                    all_words_long: all_words_long,
+                    all_words_short: all_words_short,
+                    all_words_short_set: hash_dictionary(all_words_short),
+                    // The common words list isn't in frequency order, so it can only distinguish common from uncommon
+                    all_words_long_commonness: capabilities.short && capabilities.long ? rank_commonness(all_words_long, all_words_short, false) : undefined
+                };
+                // Build the dictionaries' indices now, while everything else is loading, so the first lookup isn't slow
+                init_dictionaries(state, true).then(() => setGameState(state));
             }).catch(error => {
                 toast.current?.show({severity: "error", summary: "Error getting dictionary", detail: "There was an error getting the dictionary: " + error});
             });
@@ -48,29 +80,86 @@ export default function App() {
     /**
      * Runs the solver
      * @param letters Mapping of length-one letter strings to the number of that letter present in the hand
+     * @param resume Where a previous search of the same hand left off, if continuing it
      */
-    const startRunning = (letters: Map<string, number>) => {
+    const startRunning = (letters: Map<string, number>, resume?: SearchState) => {
         setRunning(true);
         if (gameState != null) {
-            const worker = new Worker(new URL("solver", import.meta.url), {type: "module"});
-            worker.addEventListener("message", e => {
+            if (resume == null) {
+                setBestBoard(null);
+            }
+            solveStart.current = new Date();
+            worker.current = new Worker(new URL("solver", import.meta.url), {type: "module"});
+            worker.current.addEventListener("message", e => {
                 if (typeof e.data === "string") {
                     toast.current?.show({severity: "error", summary: "Uh oh!", detail: "" + e.data});
+                    setBestBoard(null);
                     setRunning(false);
                 }
+                else if ("best_board" in e.data) {
+                    setBestBoard(e.data.best_board as BestBoard);
+                }
+                else if ("search_state" in e.data) {
+                    const timeout = e.data as SearchTimeout;
+                    confirmDialog({
+                        message: timeout.message + ". Keep searching?",
+                        header: "Still searching",
+                        icon: "pi pi-question-circle",
+                        accept: () => startRunning(letters, timeout.search_state),
+                        reject: () => {
+                            // Nothing will replace the partial board now, so drop it rather than leave it hiding the results
+                            setBestBoard(null);
+                            setRunning(false);
+                        }
+                    });
+                }
                 else {
-                    const results = e.data as result_t;
-                    const new_state: AppState = {
-                        all_words_short: gameState.all_words_short,
-                        all_words_long: gameState.all_words_long,
-                        last_game: results.state
-                    }
-                    setGameState(new_state);
-                    setResults(results);
-                    setRunning(false);
+                    showResults(e.data as result_t);
                 }
             });
-            worker.postMessage({letters: letters, gameState: gameState});
+            // Errors the solver doesn't catch itself, e.g. if the worker fails to load
+            worker.current.addEventListener("error", e => {
+                toast.current?.show({severity: "error", summary: "Uh oh!", detail: "The solver stopped unexpectedly: " + e.message});
+                setBestBoard(null);
+                setRunning(false);
+            });
+            worker.current.postMessage({letters: letters, gameState: gameState, config: SOLVER_CONFIG, resume: resume});
+        }
+    }
+
+    /**
+     * Displays the results of a solve, and keeps its board to build off of next time
+     * @param results The solver's results
+     */
+    const showResults = (results: result_t) => {
+        if (gameState != null) {
+            const new_state: AppState = {
+                all_words_short: gameState.all_words_short,
+                all_words_long: gameState.all_words_long,
+                all_words_short_set: gameState.all_words_short_set,
+                all_words_long_commonness: gameState.all_words_long_commonness,
+                word_history: record_word_history(gameState.word_history ?? [], results.state),
+                candidate_cache: results.candidate_cache,
+                last_game: results.state
+            }
+            setGameState(new_state);
+        }
+        setResults(results);
+        setBestBoard(null);
+        setRunning(false);
+    }
+
+    /**
+     * Stops the solver and keeps the best partial board it found as the result
+     */
+    const acceptBestBoard = () => {
+        worker.current?.terminate();
+        worker.current = null;
+        if (bestBoard == null) {
+            setRunning(false);
+        }
+        else {
+            showResults(best_board_solution(bestBoard, solveStart.current));
         }
     }
 
@@ -80,7 +169,8 @@ export default function App() {
     const clearResults = () => {
         if (!running && gameState != null) {
             setResults(null);
-            setGameState({last_game: null, all_words_long: gameState.all_words_long, all_words_short: gameState.all_words_short});
+            setBestBoard(null);
+            setGameState({last_game: null, all_words_long: gameState.all_words_long, all_words_short: gameState.all_words_short, all_words_short_set: gameState.all_words_short_set, all_words_long_commonness: gameState.all_words_long_commonness, word_history: gameState.word_history});
         }
     }
 
@@ -93,7 +183,8 @@ export default function App() {
                 <LetterInput gameState={gameState} toast={toast} startRunning={startRunning} running={running} contextMenu={letterInputContextMenu} setPlayableWords={setPlayableWords} setPlayableWordsVisible={setPlayableWordsVisible} clearResults={clearResults}/>
             </SplitterPanel>
             <SplitterPanel size={panelSizes[1]} style={{display: "flex", justifyContent: "center", alignItems: "center"}} pt={{root: {onContextMenu: e => setResultsContextMenu(e)}}}>
-                <ResultsDisplay toast={toast} results={results} contextMenu={resultsContextMenu} clearResults={clearResults} running={running} panelWidth={panelSizes[1]}/>
+                <ResultsDisplay toast={toast} results={bestBoard == null ? results : null} contextMenu={resultsContextMenu} clearResults={clearResults} running={running} panelWidth={panelSizes[1]}/>
+                {bestBoard == null ? null : <BestBoardPreview bestBoard={bestBoard} running={running} acceptBestBoard={acceptBestBoard}/>}
             </SplitterPanel>
         </Splitter>
         </>
@@ -0,0 +1,51 @@
+import { Button } from "primereact/button";
+import { BestBoard } from "./solver";
+
+interface BestBoardPreviewProps {
+    /**
+     * The best partial board posted by the solver so far
+     */
+    bestBoard: BestBoard,
+    /**
+     * Whether the solver is still running
+     */
+    running: boolean,
+    /**
+     * Function to stop the solver and keep `bestBoard`
+     */
+    acceptBestBoard: () => void
+}
+
+/**
+ * Displays the best partial board found so far by a long solve, so the user can settle for it
+ *
+ * @component
+ */
+export default function BestBoardPreview(props: BestBoardPreviewProps) {
+    return (
+        <div style={{display: "flex", flexDirection: "column", alignItems: "center"}}>
+            <table id="best-board-table">
+                <tbody className="results-tbody">
+                    {props.bestBoard.board.map((row, i) => {
+                        return (
+                            <tr key={"row-"+i} className="results-tr">
+                                {row.map((val, j) => {
+                                    if (val.trim() === "") {
+                                        return <td key={"row-"+i+"-cell-"+j} className="emptyCell"></td>
+                                    }
+                                    else {
+                                        return <td key={"row-"+i+"-cell-"+j} className="occupiedCell">{val.charAt(0)}</td>
+                                    }
+                                })}
+                            </tr>
+                        )
+                    })}
+                </tbody>
+            </table>
+            <span style={{marginTop: "5px"}}>
+                {props.bestBoard.leftover_letters} letter{props.bestBoard.leftover_letters === 1 ? "" : "s"} left after checking {props.bestBoard.words_checked} words
+            </span>
+            {props.running ? <Button type="button" label="Good enough" icon="pi pi-check" style={{marginTop: "5px"}} severity="secondary" onClick={props.acceptBestBoard}/> : null}
+        </div>
+    )
+}
@@ -0,0 +1,22 @@
+import { BoardWordCount } from "./solver";
+
+interface BoardStatsProps {
+    /**
+     * Counts of the words on the board
+     */
+    words: BoardWordCount
+}
+
+/**
+ * Displays statistics about the words on the solved board
+ * 
+ * @component
+ */
+export default function BoardStats(props: BoardStatsProps) {
+    return (
+        <span style={{position: "fixed", bottom: "3vh", left: "27vw"}}>
+            {props.words.total} word{props.words.total === 1 ? "" : "s"} ({props.words.horizontal} across, {props.words.vertical} down)
+            averaging {props.words.avg_length.toFixed(1)} letters; longest is {props.words.max_length}
+        </span>
+    )
+}
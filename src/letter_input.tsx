@@ -9,7 +9,7 @@ import { MenuItem } from "primereact/menuitem";
 import { Toast } from "primereact/toast";
 import { Dropdown } from "primereact/dropdown";
 import { TabPanel, TabView } from "primereact/tabview";
-import { AppState, get_playable_words, get_random_letters } from "./solver";
+import { AppState, detect_dead_end_patterns, get_playable_words, get_random_letters } from "./solver";
 import { readText, writeText } from "./utilities";
 
 interface LetterInputProps {
@@ -472,7 +472,25 @@ export default function LetterInput(props: LetterInputProps){
             UPPERCASE.forEach(c => {
                 letters.set(c, letterNums.get(c) ?? 0);
             });
-            props.startRunning(letters);
+            if (props.gameState == null) {
+                props.startRunning(letters);
+                return;
+            }
+            // Warn about hands that are very likely unsolvable before spending time trying
+            detect_dead_end_patterns(letters, props.gameState, false).then(analysis => {
+                if (analysis.patterns.length === 0) {
+                    props.startRunning(letters);
+                }
+                else {
+                    confirmDialog({
+                        message: analysis.patterns.map(pattern => pattern.description).join(". ") + ". Consider dumping " + analysis.suggested_dumps.join(" or ") + ". Solve anyway?",
+                        header: "Likely unsolvable",
+                        icon: "pi pi-exclamation-triangle",
+                        accept: () => props.startRunning(letters)
+                    });
+                }
+            })
+            .catch(() => props.startRunning(letters));
         }
     }
     
@@ -0,0 +1,20 @@
+/**
+ * Words kept off of the board when `SolverConfig.family_friendly` is set, in uppercase and in alphabetical order.
+ * Each form of a word is listed separately rather than matching substrings, so that innocent words containing them (e.g. "SCUNTHORPE") are still allowed;
+ * words with common innocent meanings (e.g. "COCK" or "TIT") are left out.
+ */
+export const OFFENSIVE_WORDS: ReadonlyArray<string> = [
+    "ARSE", "ARSES", "ASSHOLE", "ASSHOLES",
+    "BASTARD", "BASTARDS", "BITCH", "BITCHES", "BITCHY", "BOLLOCKS", "BONER", "BONERS", "BOOBIES", "BOOBS",
+    "CUM", "CUNT", "CUNTS",
+    "DILDO", "DILDOS", "DYKE", "DYKES",
+    "FAG", "FAGGOT", "FAGGOTS", "FAGS", "FUCK", "FUCKED", "FUCKER", "FUCKERS", "FUCKING", "FUCKS",
+    "GOOK", "GOOKS",
+    "KIKE", "KIKES",
+    "NIGGER", "NIGGERS",
+    "PISS", "PISSED", "PORN", "PORNO", "PUSSY",
+    "RETARD", "RETARDS",
+    "SHIT", "SHITS", "SHITTY", "SKANK", "SLUT", "SLUTS", "SPIC", "SPICS",
+    "TITS", "TITTY", "TURD", "TURDS", "TWAT", "TWATS",
+    "WANK", "WANKER", "WHORE", "WHORES", "WOP", "WOPS"
+];
@@ -1,6 +1,7 @@
 import { Dialog } from "primereact/dialog";
 import { TabView, TabPanel } from "primereact/tabview";
 import PlayableWordsList from "./playable_words_list";
+import { dictionary_capabilities } from "./solver";
 
 interface PlayableWordsProps {
     /**
@@ -33,16 +34,20 @@ interface PlayableWordsProps {
  * @component
  */
 export default function PlayableWords(props: PlayableWordsProps) {
+    // Only show the dictionaries in this build
+    const capabilities = dictionary_capabilities();
     return (
         <>
         <Dialog header="Playable words" visible={props.visible} onHide={() => props.setVisible(false)} contentStyle={{width: "35vw", maxHeight: "85vh", overflowY: "hidden"}} pt={{mask: {onContextMenu: e => e.preventDefault()}}}>
             <TabView>
+                {capabilities.short ?
                 <TabPanel header="Common words">
                     <PlayableWordsList playableWords={props.playableWords?.short} which="common"/>
-                </TabPanel>
+                </TabPanel> : null}
+                {capabilities.long ?
                 <TabPanel header="Full Scrabble dictionary">
                     <PlayableWordsList playableWords={props.playableWords?.long} which="full"/>
-                </TabPanel>
+                </TabPanel> : null}
             </TabView>
         </Dialog>
         </>
@@ -5,6 +5,8 @@ import { MenuItem } from "primereact/menuitem";
 import { Toast } from "primereact/toast";
 import html2canvas from "html2canvas";
 import SolutionTime from "./solution_time";
+import BoardStats from "./board_stats";
+import { count_words_on_board } from "./solver";
 import { result_t } from "./types";
 import { writeText } from "./utilities";
 
@@ -211,6 +213,7 @@ export default function ResultsDisplay(props: ResultsDisplayProps) {
             </tbody>
         </table>
         <SolutionTime time={props.results.elapsed} num_letters={num_letters} density={density}/>
+        <BoardStats words={count_words_on_board(props.results.state.board, props.results.state.min_col, props.results.state.max_col, props.results.state.min_row, props.results.state.max_row)}/>
         </>
         }
         </>
@@ -0,0 +1,780 @@
+import assert from "node:assert/strict";
+import { describe, it } from "node:test";
+import { AppState, BOARD_SIZE, BOARD_TOO_LARGE, Board, SolverConfig, board_from_clipboard_text, board_spread, candidate_key, convert_word_to_array, convert_words_to_arena, count_playable_words, detect_dead_end_patterns, get_playable_words, handle_worker_message, hash_dictionary, hill_climb, is_connected, letter_index, makeable_word_indices, memory_stats, play_bananagrams, play_word, score_board, score_word, solution_t, validate_board } from "./solver";
+import dictionary from "../dictionary.txt?raw";
+import short_dictionary from "../short_dictionary.txt?raw";
+import { OFFENSIVE_WORDS } from "./offensive_words";
+import { PlaySequence } from "./types";
+import { getRandomInt, seededRandom } from "./utilities";
+
+/**
+ * Counts the letters of a word, e.g. to use it as the whole hand
+ * @param word Uppercase word
+ * @returns Length-26 array of the number of each letter in `word`
+ */
+function letters_of(word: string) {
+    const letters = new Uint8Array(26);
+    convert_word_to_array(word).forEach(letter => letters[letter] += 1);
+    return letters;
+}
+
+/**
+ * Puts a single tile on a board
+ * @param board The `Board` to modify
+ * @param row Row of the tile
+ * @param col Column of the tile
+ * @param letter Uppercase letter of the tile
+ */
+function place(board: Board, row: number, col: number, letter: string) {
+    board.set_val(row, col, letter.charCodeAt(0) - 65);
+}
+
+/**
+ * Makes a dictionary of random words, weighted towards common letters so that plenty of them are makeable
+ * @param size Number of words
+ * @param random Source of random numbers (see `seededRandom`)
+ * @returns Array of words (see `convert_word_to_array`)
+ */
+function random_dictionary(size: number, random: () => number) {
+    const alphabet = "EEEEEAAAIIOONNRRTTLSSUDGBCMPFHVWYKJXQZ";
+    const words: string[] = [];
+    for (let i=0; i<size; i++) {
+        const length = getRandomInt(2, 7, random);
+        let word = "";
+        for (let j=0; j<length; j++) {
+            word += alphabet.charAt(getRandomInt(0, alphabet.length-1, random));
+        }
+        words.push(word);
+    }
+    return convert_words_to_arena(words);
+}
+
+/**
+ * Creates an app state with the same short and long dictionary
+ * @param dictionary Array of words (see `convert_word_to_array`)
+ * @returns A new `AppState` with no previous game
+ */
+function state_of(dictionary: Array<Uint8Array>): AppState {
+    return {last_game: null, all_words_short: dictionary, all_words_long: dictionary, all_words_short_set: hash_dictionary(dictionary)};
+}
+
+/**
+ * Converts a hand into the mapping the frontend passes to the solver
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @returns Mapping of every uppercase letter to how many of it are in the hand
+ */
+function available_of(letters: Uint8Array) {
+    const available = new Map<string, number>();
+    letters.forEach((count, i) => available.set(String.fromCharCode(i+65), count));
+    return available;
+}
+
+/**
+ * Checks that the solver found a valid, connected board that uses exactly the hand's tiles
+ * @param res What `play_bananagrams` returned
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @param state The app state the hand was solved with
+ * @returns The solution
+ */
+async function assert_solved(res: ReturnType<typeof play_bananagrams>, letters: Uint8Array, state: AppState): Promise<solution_t> {
+    assert.equal(typeof res === "string" ? res : "board" in res ? null : res.message, null);
+    const solution = res as solution_t;
+    const {board, min_col, max_col, min_row, max_row} = solution.state;
+    // Exactly the hand's tiles are on the board
+    const on_board = new Uint8Array(26);
+    for (let row=min_row; row<=max_row; row++) {
+        for (let col=min_col; col<=max_col; col++) {
+            const val = board[row*BOARD_SIZE + col];
+            if (val < 26) {
+                on_board[val] += 1;
+            }
+        }
+    }
+    assert.deepEqual(Array.from(on_board), Array.from(letters));
+    const wrapped = new Board();
+    wrapped.arr.set(board);
+    assert.ok(is_connected(wrapped, min_col, max_col, min_row, max_row));
+    assert.deepEqual(await validate_board(board, min_col, max_col, min_row, max_row, state, false), []);
+    return solution;
+}
+
+describe("play_word", () => {
+    const cat = convert_word_to_array("CAT");
+
+    it("plays a word that only touches another tile at its end", () => {
+        const board = new Board();
+        place(board, 10, 13, "S");
+        const res = play_word(cat, 10, 10, board, "horizontal", letters_of("CAT"));
+        assert.notEqual(res, null);
+        assert.ok(res![0]);
+        assert.equal(res![3], "Finished");
+    });
+
+    it("plays a word that only touches another tile at its start", () => {
+        const board = new Board();
+        place(board, 9, 10, "S");
+        const res = play_word(cat, 10, 10, board, "vertical", letters_of("CAT"));
+        assert.ok(res![0]);
+    });
+
+    it("doesn't play a word one cell away from every tile", () => {
+        const board = new Board();
+        place(board, 10, 14, "S");
+        const res = play_word(cat, 10, 10, board, "horizontal", letters_of("CAT"));
+        assert.equal(res![0], false);
+        assert.equal(res![1].length, 0);
+    });
+
+    it("plays a word ending in the last column", () => {
+        const board = new Board();
+        place(board, 5, BOARD_SIZE-4, "S");
+        const res = play_word(cat, 5, BOARD_SIZE-3, board, "horizontal", letters_of("CAT"));
+        assert.ok(res![0]);
+        assert.equal(board.get_val(5, BOARD_SIZE-1), cat[2]);
+    });
+
+    it("plays a word ending in the last row", () => {
+        const board = new Board();
+        place(board, BOARD_SIZE-4, 5, "S");
+        const res = play_word(cat, BOARD_SIZE-3, 5, board, "vertical", letters_of("CAT"));
+        assert.ok(res![0]);
+        assert.equal(board.get_val(BOARD_SIZE-1, 5), cat[2]);
+    });
+
+    it("plays a word along the last row next to a tile above it", () => {
+        const board = new Board();
+        place(board, BOARD_SIZE-2, 11, "A");
+        const res = play_word(cat, BOARD_SIZE-1, 10, board, "horizontal", letters_of("CAT"));
+        assert.ok(res![0]);
+    });
+
+    // Each word reaches a corner of the board, touching a tile next to its last letter
+    const corners = [
+        {row: 0, col: 0, direction: "horizontal", tile: [1, 2], corner: [0, 0]},
+        {row: 0, col: BOARD_SIZE-3, direction: "horizontal", tile: [1, BOARD_SIZE-1], corner: [0, BOARD_SIZE-1]},
+        {row: BOARD_SIZE-3, col: 0, direction: "vertical", tile: [BOARD_SIZE-1, 1], corner: [BOARD_SIZE-1, 0]},
+        {row: BOARD_SIZE-1, col: BOARD_SIZE-3, direction: "horizontal", tile: [BOARD_SIZE-2, BOARD_SIZE-1], corner: [BOARD_SIZE-1, BOARD_SIZE-1]}
+    ] as const;
+    for (const {row, col, direction, tile, corner} of corners) {
+        it("plays a word starting at (" + row + ", " + col + ") " + direction + "ly into the corner at (" + corner.join(", ") + ")", () => {
+            const board = new Board();
+            place(board, tile[0], tile[1], "S");
+            const res = play_word(cat, row, col, board, direction, letters_of("CAT"));
+            assert.ok(res![0]);
+            assert.equal(res![3], "Finished");
+            assert.equal(board.get_val(row, col), cat[0]);
+            assert.ok(res![1].some(([r, c]) => r === corner[0] && c === corner[1]));
+        });
+    }
+
+    it("returns null for a word running past the last column or row", () => {
+        const board = new Board();
+        place(board, 5, BOARD_SIZE-3, "S");
+        assert.equal(play_word(cat, 5, BOARD_SIZE-2, board, "horizontal", letters_of("CAT")), null);
+        assert.equal(play_word(cat, BOARD_SIZE-2, 5, board, "vertical", letters_of("CAT")), null);
+        // Nothing is played when the word doesn't fit
+        assert.equal(board.get_val(5, BOARD_SIZE-2), new Board().get_val(5, BOARD_SIZE-2));
+    });
+
+    it("returns null for negative starts", () => {
+        const board = new Board();
+        place(board, 0, 0, "S");
+        assert.equal(play_word(cat, -1, 0, board, "horizontal", letters_of("CAT")), null);
+        assert.equal(play_word(cat, 0, -1, board, "horizontal", letters_of("CAT")), null);
+        assert.equal(play_word(cat, -1, 0, board, "vertical", letters_of("CAT")), null);
+        assert.equal(play_word(cat, 0, -1, board, "vertical", letters_of("CAT")), null);
+    });
+
+    it("doesn't play a zero-length word", () => {
+        const board = new Board();
+        place(board, 10, 9, "S");
+        const res = play_word(new Uint8Array(0), 10, 10, board, "horizontal", letters_of("CAT"));
+        assert.equal(res![0], false);
+        assert.deepEqual(res![1], []);
+        assert.deepEqual(Array.from(res![2]), Array.from(letters_of("CAT")));
+    });
+
+    it("doesn't play a word that entirely overlaps tiles already on the board", () => {
+        const board = new Board();
+        place(board, 10, 10, "C");
+        place(board, 10, 11, "A");
+        place(board, 10, 12, "T");
+        place(board, 11, 10, "S");
+        const res = play_word(cat, 10, 10, board, "horizontal", letters_of("CAT"));
+        assert.equal(res![0], false);
+        assert.deepEqual(res![1], []);
+        assert.equal(res![3], "Remaining");
+        assert.deepEqual(Array.from(res![2]), Array.from(letters_of("CAT")));
+    });
+
+    it("doesn't play a word that overlaps a different letter", () => {
+        const board = new Board();
+        place(board, 9, 12, "O");
+        place(board, 10, 12, "R");
+        const res = play_word(cat, 10, 10, board, "horizontal", letters_of("CAT"));
+        assert.equal(res![0], false);
+        assert.equal(res![3], "Remaining");
+        // The tiles played before the mismatch are returned so that the caller can undo them
+        assert.deepEqual(res![1], [[10, 10], [10, 11]]);
+        assert.equal(board.get_val(10, 12), "R".charCodeAt(0) - 65);
+    });
+
+    it("returns null for starts past the end of the board", () => {
+        const board = new Board();
+        assert.equal(play_word(cat, BOARD_SIZE, 0, board, "horizontal", letters_of("CAT")), null);
+        assert.equal(play_word(cat, 0, BOARD_SIZE, board, "vertical", letters_of("CAT")), null);
+    });
+});
+
+describe("hill_climb", () => {
+    it("keeps the bounds inside the grid when moving a word next to the bottom-right corner", () => {
+        const opening = "TBBBTB";
+        const hanging = "XYT";
+        const board = new Board();
+        // The opening word ends in the corner, and the vertical word hangs from its first T
+        [...opening].forEach((letter, i) => place(board, BOARD_SIZE-1, BOARD_SIZE-opening.length+i, letter));
+        [...hanging].forEach((letter, i) => place(board, BOARD_SIZE-hanging.length+i, BOARD_SIZE-opening.length, letter));
+        const play_sequence: PlaySequence = [
+            [convert_word_to_array(opening), [BOARD_SIZE-1, BOARD_SIZE-opening.length, "horizontal"]],
+            [convert_word_to_array(hanging), [BOARD_SIZE-hanging.length, BOARD_SIZE-opening.length, "vertical"]]
+        ];
+        const words_set = hash_dictionary(convert_words_to_arena([opening, hanging]));
+        const [rounds, min_col, max_col, min_row, max_row] = hill_climb(board, BOARD_SIZE-opening.length, BOARD_SIZE-1, BOARD_SIZE-hanging.length, BOARD_SIZE-1, play_sequence, words_set, {}, board_spread, Infinity);
+        // Hanging from the second T, one column from the edge, is more compact
+        assert.equal(rounds, 1);
+        assert.deepEqual(play_sequence[1][1], [BOARD_SIZE-hanging.length, BOARD_SIZE-2, "vertical"]);
+        assert.deepEqual([min_col, max_col, min_row, max_row], [BOARD_SIZE-opening.length, BOARD_SIZE-1, BOARD_SIZE-hanging.length, BOARD_SIZE-1]);
+    });
+});
+
+describe("makeable_word_indices", () => {
+    it("extends the cache to the same words as checking every word, over random changes to the hand", () => {
+        const random = seededRandom(900);
+        const state = state_of(random_dictionary(3000, random));
+        let letters = new Uint8Array(26);
+        for (let step=0; step<300; step++) {
+            letters = letters.slice();
+            const change = random();
+            if (change < 0.7) {
+                // Peel, which is what the cache is for
+                for (let i=getRandomInt(1, 3, random); i>0; i--) {
+                    letters[getRandomInt(0, 25, random)] += 1;
+                }
+            }
+            else if (change < 0.85) {
+                // Lose letters, e.g. when solving part of the hand
+                const held = Array.from(letters.keys()).filter(i => letters[i] > 0);
+                if (held.length > 0) {
+                    letters[held[getRandomInt(0, held.length-1, random)]] -= 1;
+                }
+            }
+            else {
+                // Dump, which both gains and loses letters
+                letters[getRandomInt(0, 25, random)] += 3;
+                letters[getRandomInt(0, 25, random)] = 0;
+            }
+            const config: SolverConfig = {no_two_letter_words: random() < 0.1};
+            const [indices] = makeable_word_indices(state, letters, config);
+            const [expected, hit] = makeable_word_indices(state_of(state.all_words_short), letters, config);
+            assert.equal(hit, false);
+            assert.deepEqual(Array.from(indices), Array.from(expected));
+            state.candidate_cache = {letters: letters, key: candidate_key(state, config), indices: indices};
+        }
+    });
+
+    it("doesn't use a cache built from a different dictionary of the same size", () => {
+        const random = seededRandom(9003);
+        const first = state_of(random_dictionary(500, random));
+        const second = state_of(random_dictionary(500, random));
+        const letters = new Uint8Array(26).fill(2);
+        const [indices] = makeable_word_indices(first, letters, {});
+        second.candidate_cache = {letters: letters, key: candidate_key(first, {}), indices: indices};
+        const [cached, hit] = makeable_word_indices(second, letters, {});
+        assert.equal(hit, false);
+        assert.deepEqual(Array.from(cached), Array.from(makeable_word_indices(state_of(second.all_words_short), letters, {})[0]));
+    });
+});
+
+describe("letter_index", () => {
+    it("lists the same words and counts as scanning every word", () => {
+        const dictionary = random_dictionary(2000, seededRandom(901));
+        const index = letter_index(dictionary);
+        for (let letter=0; letter<26; letter++) {
+            const words: number[] = [];
+            const counts: number[] = [];
+            dictionary.forEach((word, i) => {
+                const count = word.filter(l => l === letter).length;
+                if (count > 0) {
+                    words.push(i);
+                    counts.push(count);
+                }
+            });
+            assert.deepEqual(Array.from(index.words[letter]), words);
+            assert.deepEqual(Array.from(index.counts[letter]), counts);
+        }
+    });
+
+    it("is built once per dictionary", () => {
+        const dictionary = random_dictionary(100, seededRandom(9012));
+        assert.equal(letter_index(dictionary), letter_index(dictionary));
+    });
+});
+
+describe("play_bananagrams", () => {
+    const all_words_short = convert_words_to_arena(short_dictionary.split("\n").filter(word => word.length > 1).map(word => word.toUpperCase().trim()));
+    const state = state_of(all_words_short);
+
+    /**
+     * Hands that are known to be solvable with the common words. Each is laid out as the first word across, with the rest hanging down
+     * from every other letter of it (so that they don't touch each other), and `shared` is the letters the words cross at.
+     */
+    const hands = [
+        {name: "a single word", words: ["CAT"], shared: ""},
+        // ELEPHANT across; YELLOW, QUEEN, HOUSE and ISLAND down
+        {name: "26 tiles with a Q", words: ["ELEPHANT", "YELLOW", "QUEEN", "HOUSE", "ISLAND"], shared: "EEHN"},
+        // AVENUE across; AUDIO, IDEA and QUEUE down; OCEAN across from the bottom of AUDIO
+        {name: "21 tiles, two thirds of them vowels", words: ["AVENUE", "AUDIO", "IDEA", "QUEUE", "OCEAN"], shared: "AEUO"},
+        // STRENGTH across; SCRIPT, RHYTHM, LYNCH and TRY down
+        {name: "24 tiles with only two vowels", words: ["STRENGTH", "SCRIPT", "RHYTHM", "LYNCH", "TRY"], shared: "SRNT"},
+        // BANANAS across; BANANA, NINE, NOON and SEASON down
+        {name: "23 tiles with many repeated letters", words: ["BANANAS", "BANANA", "NINE", "NOON", "SEASON"], shared: "BNNS"},
+        // PIZZA across; PURPLE, ZEBRA and JAZZ down; AQUA across from the bottom of ZEBRA
+        {name: "20 tiles with J, Q and four Zs", words: ["PIZZA", "PURPLE", "ZEBRA", "JAZZ", "AQUA"], shared: "PZAA"}
+    ];
+
+    /**
+     * Counts the tiles of a hand
+     * @param hand One of `hands`
+     * @returns Length-26 array of the number of each letter in the hand
+     */
+    const hand_letters = (hand: typeof hands[number]) => {
+        const letters = new Uint8Array(26);
+        hand.words.forEach(word => convert_word_to_array(word).forEach(letter => letters[letter] += 1));
+        convert_word_to_array(hand.shared).forEach(letter => letters[letter] -= 1);
+        return letters;
+    };
+
+    for (const hand of hands) {
+        it("solves " + hand.name, {timeout: 60000}, async () => {
+            const letters = hand_letters(hand);
+            await assert_solved(play_bananagrams(available_of(letters), state, {max_words_to_check: 1000000}), letters, state);
+        });
+    }
+
+    it("keeps every polished board connected, made of real words and using every tile", {timeout: 120000}, async () => {
+        let moved = 0;
+        for (const hand of hands) {
+            const letters = hand_letters(hand);
+            const solution = await assert_solved(play_bananagrams(available_of(letters), state, {max_words_to_check: 1000000, polish: true}), letters, state);
+            assert.ok(solution.stats.polish_score_delta >= 0);
+            if (solution.stats.polish_iterations > 0) {
+                moved += 1;
+            }
+        }
+        // At least one board was changed by polishing
+        assert.ok(moved > 0);
+    });
+
+    it("keeps a tile in the first and last row and column of the bounds", () => {
+        // HOUSE and GARDEN crossing at the E, so at least one word is played off the opening word
+        const res = play_bananagrams(available_of(letters_of("HOUSGARDEN")), state, {max_words_to_check: 1000000});
+        assert.equal(typeof res === "string" ? res : "board" in res ? null : res.message, null);
+        if (typeof res === "string" || !("board" in res)) {
+            return;
+        }
+        const {board, min_col, max_col, min_row, max_row} = res.state;
+        const rows = Array.from({length: max_row-min_row+1}, (_, i) => min_row + i);
+        const cols = Array.from({length: max_col-min_col+1}, (_, i) => min_col + i);
+        /**
+         * Checks whether a cell has a tile
+         * @param row Row of the cell
+         * @param col Column of the cell
+         * @returns Whether the cell isn't empty
+         */
+        const occupied = (row: number, col: number) => board[row*BOARD_SIZE + col] < 26;
+        assert.ok(rows.some(row => occupied(row, max_col)));
+        assert.ok(cols.some(col => occupied(max_row, col)));
+        assert.ok(rows.some(row => occupied(row, min_col)));
+        assert.ok(cols.some(col => occupied(min_row, col)));
+    });
+
+    it("meets min_intersection_density", {timeout: 60000}, async () => {
+        const letters = letters_of("HOUSGARDEN");
+        const sparse = await assert_solved(play_bananagrams(available_of(letters), state, {max_words_to_check: 200000}), letters, state);
+        assert.ok(sparse.quality.intersection_count/sparse.quality.tile_count < 0.3);
+        const dense = await assert_solved(play_bananagrams(available_of(letters), state, {max_words_to_check: 200000, min_intersection_density: 0.3}), letters, state);
+        assert.ok(dense.quality.intersection_count/dense.quality.tile_count >= 0.3);
+    });
+
+    it("finds the same boards without the coverage prune, over random hands", {timeout: 60000}, () => {
+        const random = seededRandom(902);
+        const random_state = state_of(random_dictionary(1000, random));
+        const alphabet = "EEEEEAAAIIOONNRRTTLSSUDGBCMPFHVWYKJXQZ";
+        let pruned_hands = 0;
+        for (let hand=0; hand<40; hand++) {
+            const letters = new Uint8Array(26);
+            for (let i=getRandomInt(5, 9, random); i>0; i--) {
+                letters[alphabet.charCodeAt(getRandomInt(0, alphabet.length-1, random)) - 65] += 1;
+            }
+            const pruned = play_bananagrams(available_of(letters), random_state, {max_words_to_check: 2000000});
+            const unpruned = play_bananagrams(available_of(letters), random_state, {max_words_to_check: 2000000, no_coverage_prune: true});
+            if (typeof pruned === "string" || typeof unpruned === "string") {
+                // Every board the prune gave up on also failed without it
+                assert.equal(unpruned, pruned);
+                continue;
+            }
+            assert.ok("board" in pruned && "board" in unpruned);
+            // Otherwise the search would have found a board in one of the pruned subtrees first
+            assert.deepEqual(unpruned.board, pruned.board);
+            if (pruned.stats.pruned_subtrees > 0) {
+                pruned_hands += 1;
+                assert.ok(unpruned.stats.words_checked > pruned.stats.words_checked);
+            }
+        }
+        assert.ok(pruned_hands > 0);
+    });
+
+    it("finds the same boards checking words against only the makeable ones, over random hands", {timeout: 60000}, () => {
+        const random = seededRandom(880);
+        const alphabet = "EEEEEAAAIIOONNRRTTLSSUDGBCMPFHVWYKJXQZ";
+        let solved_hands = 0;
+        for (let hand=0; hand<20; hand++) {
+            const letters = new Uint8Array(26);
+            for (let i=getRandomInt(8, 15, random); i>0; i--) {
+                letters[alphabet.charCodeAt(getRandomInt(0, alphabet.length-1, random)) - 65] += 1;
+            }
+            // Every tile on a board comes from the hand, so every word on it is makeable and no lookup can differ
+            const [indices] = makeable_word_indices(state, letters, {});
+            const makeable_state = {...state, all_words_short_set: hash_dictionary(Array.from(indices, i => all_words_short[i]))};
+            const full = play_bananagrams(available_of(letters), state, {max_words_to_check: 20000});
+            const makeable = play_bananagrams(available_of(letters), makeable_state, {max_words_to_check: 20000});
+            if (typeof full === "string" || typeof makeable === "string" || !("board" in full) || !("board" in makeable)) {
+                assert.equal(typeof makeable, typeof full);
+                continue;
+            }
+            solved_hands += 1;
+            assert.deepEqual(makeable.board, full.board);
+            assert.equal(makeable.stats.words_checked, full.stats.words_checked);
+        }
+        assert.ok(solved_hands > 0);
+    });
+
+    it("solves without the letter index when over max_memory_bytes", {timeout: 60000}, async () => {
+        // A copy of the dictionary, so that no other test has built its index
+        const memory_state = state_of(all_words_short.slice());
+        const first = await assert_solved(play_bananagrams(available_of(letters_of("HOUSGARDEN")), memory_state, {max_words_to_check: 200000}), letters_of("HOUSGARDEN"), memory_state);
+        // Peeling extends the cached candidates using the index
+        const index_bytes = memory_stats().index_bytes;
+        const letters = letters_of("HOUSGARDENST");
+        const indexed = await assert_solved(play_bananagrams(available_of(letters), {...memory_state, candidate_cache: first.candidate_cache}, {max_words_to_check: 200000}), letters, memory_state);
+        assert.ok(indexed.stats.candidate_cache_hit);
+        assert.ok(memory_stats().index_bytes > index_bytes);
+        // Over the limit, the index is dropped and every word is checked instead, which finds the same words
+        const limited = await assert_solved(play_bananagrams(available_of(letters), {...memory_state, candidate_cache: first.candidate_cache}, {max_words_to_check: 200000, max_memory_bytes: 1}), letters, memory_state);
+        assert.equal(limited.stats.candidate_cache_hit, false);
+        assert.equal(memory_stats().index_bytes, 0);
+        assert.deepEqual(limited.board, indexed.board);
+    });
+
+    // Offensive words that can only be formed where other words cross, since none of them can be played
+    const playable_words = all_words_short.filter(word => !OFFENSIVE_WORDS.includes(String.fromCharCode(...Array.from(word, letter => letter + 65))));
+    const crossing_state: AppState = {...state, all_words_short: playable_words, all_words_long: playable_words};
+    for (const hand of [{letters: "TITSA", played: true, state: state}, {letters: "BOOBSAORO", played: false, state: crossing_state}]) {
+        it("keeps offensive words off of the board for " + hand.letters + " with family_friendly", {timeout: 60000}, async () => {
+            const letters = letters_of(hand.letters);
+            /**
+             * Gets the offensive words on a board
+             * @param solution The solved board
+             * @returns Each horizontal and vertical run of two or more letters that's in `OFFENSIVE_WORDS`, and whether it was played rather than only crossed
+             */
+            const offensive_words = (solution: solution_t) => {
+                const rows = solution.board.map(row => row.map(cell => cell.trim() || " ").join(""));
+                const cols = solution.board[0].map((_, col) => solution.board.map(row => row[col].trim() || " ").join(""));
+                const played = solution.state.play_sequence.map(([word]) => String.fromCharCode(...Array.from(word, letter => letter + 65)));
+                return rows.concat(cols).flatMap(line => line.split(" ")).filter(word => OFFENSIVE_WORDS.includes(word)).map(word => [word, played.includes(word)]);
+            };
+            // Without the flag, the board has one (either played, or only formed where words cross)
+            const unfiltered = await assert_solved(play_bananagrams(available_of(letters), hand.state, {max_words_to_check: 200000}), letters, hand.state);
+            assert.ok(offensive_words(unfiltered).some(([, played]) => played === hand.played));
+            const filtered = await assert_solved(play_bananagrams(available_of(letters), hand.state, {max_words_to_check: 200000, family_friendly: true}), letters, hand.state);
+            assert.deepEqual(offensive_words(filtered), []);
+        });
+    }
+
+    it("plays later words through the opening word's letters, up to filter_letters_on_board of them", async () => {
+        // CAT and TIN can only be joined at the T, which whichever is played second has to play through
+        const through_state = state_of(convert_words_to_arena(["CAT", "TIN"]));
+        const letters = letters_of("CATIN");
+        await assert_solved(play_bananagrams(available_of(letters), through_state, {}), letters, through_state);
+        assert.equal(typeof play_bananagrams(available_of(letters), through_state, {filter_letters_on_board: 0}), "string");
+    });
+
+    it("suggests allowing two-letter words when every board needs one", async () => {
+        // ABC and DEF share no letters, so they can only be joined side by side, crossed by AD, BE and CF
+        const two_letter_state = state_of(convert_words_to_arena(["ABC", "DEF", "AD", "BE", "CF"]));
+        const letters = letters_of("ABCDEF");
+        const solution = await assert_solved(play_bananagrams(available_of(letters), two_letter_state, {}), letters, two_letter_state);
+        assert.deepEqual(solution.board.filter(row => row.some(cell => cell.trim() !== "")).map(row => row.join("").replace(/\s/g, "")), ["ABC", "DEF"]);
+        const res = play_bananagrams(available_of(letters), two_letter_state, {no_two_letter_words: true});
+        assert.equal(res, "No board was found that meets the solver's options - try allowing two-letter words (no_two_letter_words), or dump");
+    });
+
+    for (const mode of ["hard", "soft"] as const) {
+        it("builds a denser board with min_intersections_per_word in " + mode + " mode", {timeout: 60000}, async () => {
+            const letters = letters_of("TEATEAEATSTARE");
+            const sparse = await assert_solved(play_bananagrams(available_of(letters), state, {max_words_to_check: 200000}), letters, state);
+            const dense = await assert_solved(play_bananagrams(available_of(letters), state, {max_words_to_check: 200000, min_intersections_per_word: 2, intersection_mode: mode}), letters, state);
+            assert.ok(dense.quality.intersection_count/dense.quality.tile_count > sparse.quality.intersection_count/sparse.quality.tile_count);
+        });
+    }
+
+    it("opens with the longest word with the longest_word strategy", async () => {
+        // In alphabetical order (e.g. a custom dictionary), AMOUNT is tried first, leaving the I and N for TIN
+        const alphabetical = state_of(convert_words_to_arena(["AMOUNT", "MOUNTAIN", "TIN"]));
+        const letters = letters_of("MOUNTAIN");
+        /**
+         * Gets the word a board was opened with
+         * @param solution The solved board
+         * @returns The first word played
+         */
+        const opening_word = (solution: solution_t) => String.fromCharCode(...Array.from(solution.state.play_sequence[0][0], letter => letter + 65));
+        const by_default = await assert_solved(play_bananagrams(available_of(letters), alphabetical, {strategy: "default"}), letters, alphabetical);
+        assert.equal(opening_word(by_default), "AMOUNT");
+        const longest = await assert_solved(play_bananagrams(available_of(letters), alphabetical, {strategy: "longest_word"}), letters, alphabetical);
+        assert.equal(opening_word(longest), "MOUNTAIN");
+        assert.equal(longest.showcased_word, "MOUNTAIN");
+    });
+
+    it("suggests lowering min_intersection_density when it can't be met", () => {
+        // A single word has no intersections at all
+        const res = play_bananagrams(available_of(letters_of("CATS")), state, {max_words_to_check: 200000, min_intersection_density: 0.3});
+        assert.equal(typeof res, "string");
+        assert.match(res as string, /lowering min_intersection_density/);
+    });
+
+    it("suggests lowering min_width when no board is wide enough", () => {
+        // Four tiles can't span five columns
+        const res = play_bananagrams(available_of(letters_of("CATS")), state, {max_words_to_check: 200000, min_width: 5});
+        assert.equal(res, "No board was found that meets the solver's options - try lowering min_width and min_height, or dump");
+    });
+
+    it("suggests raising max_word_count when the hand needs more words", () => {
+        // CATOW can only be played as CAT crossing COW
+        const two_word_state = state_of(convert_words_to_arena(["CAT", "COW"]));
+        const res = play_bananagrams(available_of(letters_of("CATOW")), two_word_state, {max_word_count: 1});
+        assert.equal(res, "No board was found that meets the solver's options - try raising max_word_count, or dump");
+    });
+
+    it("says the board is too large for the grid when it can only grow past the edge", () => {
+        // The long word spans the whole width, and CAB can only be played through its first two letters, one column past the left edge
+        const long_word = "AB".repeat(BOARD_SIZE/2);
+        const edge_state = state_of(convert_words_to_arena([long_word, "CAB"]));
+        const res = play_bananagrams(available_of(letters_of(long_word + "C")), edge_state, {strategy: "default", filter_letters_on_board: 2});
+        assert.equal(typeof res, "string");
+        assert.ok((res as string).startsWith(BOARD_TOO_LARGE));
+        // A board that never gets near the edge fails with the usual message, here because the second C can't be played
+        const short_res = play_bananagrams(available_of(letters_of("ABCC")), edge_state, {strategy: "default"});
+        assert.equal(short_res, "No valid words can be formed from the current letters - dump and try again!");
+    });
+});
+
+describe("custom dictionaries", () => {
+    const letters = letters_of("CATOW");
+    const cat_cow = state_of(convert_words_to_arena(["CAT", "COW"]));
+    const cat_two = state_of(convert_words_to_arena(["CAT", "TWO"]));
+
+    it("plays only the words of the dictionary it's given", async () => {
+        const with_cow = await assert_solved(play_bananagrams(available_of(letters), cat_cow, {}), letters, cat_cow);
+        const with_two = await assert_solved(play_bananagrams(available_of(letters), cat_two, {}), letters, cat_two);
+        // Each board has a word that the other dictionary doesn't have
+        const {board, min_col, max_col, min_row, max_row} = with_cow.state;
+        assert.notDeepEqual(await validate_board(board, min_col, max_col, min_row, max_row, cat_two, false), []);
+        const other = with_two.state;
+        assert.notDeepEqual(await validate_board(other.board, other.min_col, other.max_col, other.min_row, other.max_row, cat_cow, false), []);
+    });
+
+    it("reuses the candidate cache only for the same words", async () => {
+        const first = await assert_solved(play_bananagrams(available_of(letters), cat_cow, {}), letters, cat_cow);
+        // A copy of the same words, as when the dictionary is posted to the worker again
+        const copy = state_of(convert_words_to_arena(["CAT", "COW"]));
+        const again = await assert_solved(play_bananagrams(available_of(letters), {...copy, candidate_cache: first.candidate_cache}, {}), letters, copy);
+        assert.ok(again.stats.candidate_cache_hit);
+        const switched = await assert_solved(play_bananagrams(available_of(letters), {...cat_two, candidate_cache: first.candidate_cache}, {}), letters, cat_two);
+        assert.equal(switched.stats.candidate_cache_hit, false);
+    });
+
+    it("finds no board when no word of the dictionary plays the W", () => {
+        const res = play_bananagrams(available_of(letters), state_of(convert_words_to_arena(["CAT", "COT"])), {max_words_to_check: 1000});
+        assert.equal(typeof res, "string");
+    });
+});
+
+describe("board_from_clipboard_text", () => {
+    /**
+     * Reads a pasted board back as text
+     * @param text The pasted text
+     * @returns Each row of the board within its bounds, with a space for each empty cell
+     */
+    const read = async (text: string) => {
+        const {board, min_col, max_col, min_row, max_row} = await board_from_clipboard_text(text);
+        return Array.from({length: max_row-min_row+1}, (_, r) => Array.from(board.subarray((min_row+r)*BOARD_SIZE + min_col, (min_row+r)*BOARD_SIZE + max_col + 1), val => val < 26 ? String.fromCharCode(val + 65) : " ").join(""));
+    };
+
+    it("reads rows of letters with spaces for empty cells", async () => {
+        assert.deepEqual(await read("CAT\n  O\n  E"), ["CAT", "  O", "  E"]);
+    });
+
+    it("reads a tab before a word as an empty cell", async () => {
+        assert.deepEqual(await read("\tCAT\nTOE"), [" CAT", "TOE "]);
+    });
+
+    it("reads tab-separated cells from a spreadsheet", async () => {
+        assert.deepEqual(await read("C\tA\tT\nA\t\tO\nB\t\tE"), ["CAT", "A O", "B E"]);
+    });
+
+    it("mixes tabs and spaces", async () => {
+        assert.deepEqual(await read("\t\tA\n  T\nCAT"), ["  A", "  T", "CAT"]);
+    });
+
+    it("rejects anything but letters", async () => {
+        await assert.rejects(board_from_clipboard_text("C4T"), /Line 1 contains "4", which isn't a letter/);
+    });
+});
+
+describe("scoring", () => {
+    /**
+     * Puts a word on a board
+     * @param board The `Board` to modify
+     * @param row Row of the first letter
+     * @param col Column of the first letter
+     * @param direction Direction of the word
+     * @param word Uppercase word
+     */
+    const place_word = (board: Board, row: number, col: number, direction: "horizontal"|"vertical", word: string) => {
+        [...word].forEach((letter, i) => place(board, direction === "horizontal" ? row : row+i, direction === "horizontal" ? col+i : col, letter));
+    };
+
+    it("scores a word by its letter values, ignoring case and anything but letters", () => {
+        assert.equal(score_word("CAT"), 5);
+        assert.equal(score_word("quiz"), 22);
+        assert.equal(score_word("Q-I"), 11);
+        assert.equal(score_word(""), 0);
+    });
+
+    it("counts a crossing tile once for each word", () => {
+        const board = new Board();
+        place_word(board, 10, 10, "horizontal", "CAT");
+        place_word(board, 10, 10, "vertical", "COW");
+        // CAT (5) and COW (8) share the C (3)
+        assert.equal(score_board(board.arr, 10, 12, 10, 12), 13);
+    });
+
+    it("doesn't score a lone tile, since it isn't a word", () => {
+        const board = new Board();
+        place(board, 10, 10, "Q");
+        assert.equal(score_board(board.arr, 10, 10, 10, 10), 0);
+    });
+
+    it("sums every word on a board", () => {
+        const board = new Board();
+        // A ring: CATS and WARN across, COW and SUN down between their ends
+        place_word(board, 10, 10, "horizontal", "CATS");
+        place_word(board, 10, 10, "vertical", "COW");
+        place_word(board, 10, 13, "vertical", "SUN");
+        place_word(board, 12, 10, "horizontal", "WARN");
+        // CATS (6) + COW (8) + SUN (3) + WARN (7), so the corners count twice
+        assert.equal(score_board(board.arr, 10, 13, 10, 12), 24);
+    });
+});
+
+describe("count_playable_words", () => {
+    const words = (dictionary: string) => convert_words_to_arena(dictionary.split("\n").filter(word => word.length > 1).map(word => word.toUpperCase().trim()));
+    const state: AppState = {last_game: null, all_words_short: words(short_dictionary), all_words_long: words(dictionary), all_words_short_set: new Set()};
+
+    for (const hand of ["CAT", "HOUSGARDEN", "AEIOUSTRLN", "ELEPHANTYELLOWQUEEN", "QXZJ"]) {
+        it("agrees with get_playable_words for " + hand, async () => {
+            const available = available_of(letters_of(hand));
+            const counts = await count_playable_words(available, state);
+            const playable = await get_playable_words(available, state);
+            assert.equal(counts.short, playable.short.length);
+            assert.equal(counts.long, playable.long.length);
+            assert.equal(counts.short, playable.total_short);
+            assert.equal(counts.long, playable.total_long);
+            /**
+             * Counts words by length
+             * @param list The words
+             * @returns Number of words of each length (indexed by length)
+             */
+            const by_length = (list: string[]) => {
+                const lengths: number[] = [];
+                list.forEach(word => lengths[word.length] = (lengths[word.length] ?? 0) + 1);
+                return Array.from(lengths, n => n ?? 0);
+            };
+            assert.deepEqual(counts.short_by_length, by_length(playable.short));
+            assert.deepEqual(counts.long_by_length, by_length(playable.long));
+        });
+    }
+});
+
+describe("handle_worker_message", () => {
+    it("reports a thrown error with a summary of the inputs", () => {
+        // Solving without an app state throws
+        const res = handle_worker_message({letters: available_of(letters_of("CAT")), gameState: null}, () => {});
+        assert.equal(typeof res, "string");
+        assert.match(res as string, /^The solver hit an unexpected error: TypeError/);
+        assert.match(res as string, /\(hand: A=1 B=0 C=1 .* Z=0; tiles on the previous board: 0\)$/);
+    });
+
+    it("says so when the inputs can't be read either", () => {
+        // A plain object rather than a `Map`
+        const res = handle_worker_message({letters: {A: 1}, gameState: null}, () => {});
+        assert.match(res as string, /^The solver hit an unexpected error: .*\(inputs couldn't be read\)$/s);
+    });
+
+    it("posts the best board so far through post_message if streaming", async () => {
+        const state = state_of(convert_words_to_arena(["CAT", "COW"]));
+        for (const stream_best_boards of [false, true]) {
+            const posted: any[] = [];
+            const res = handle_worker_message({letters: available_of(letters_of("CATOW")), gameState: state, config: {stream_best_boards: stream_best_boards}}, message => posted.push(message));
+            const solution = await assert_solved(res, letters_of("CATOW"), state);
+            if (!stream_best_boards) {
+                assert.deepEqual(posted, []);
+                continue;
+            }
+            // The opening word is placed before the search tracks the best board, so COW crossing it is the one improvement
+            assert.equal(posted.length, 1);
+            assert.equal(posted[0].best_board.leftover_letters, 0);
+            assert.deepEqual(posted[0].best_board.board, solution.board);
+        }
+    });
+
+    it("doesn't post a complete board that the options reject", () => {
+        const state = state_of(convert_words_to_arena(["CAT", "COW"]));
+        const posted: any[] = [];
+        // CAT crossing COW is only three columns wide
+        const res = handle_worker_message({letters: available_of(letters_of("CATOW")), gameState: state, config: {stream_best_boards: true, min_width: 4}}, message => posted.push(message));
+        assert.equal(res, "No board was found that meets the solver's options - try lowering min_width and min_height, or dump");
+        assert.deepEqual(posted, []);
+    });
+});
+
+describe("detect_dead_end_patterns", () => {
+    const state = state_of(convert_words_to_arena(["JAM", "JOB", "GYM"]));
+
+    it("doesn't flag two Js that fit in separate words", async () => {
+        const analysis = await detect_dead_end_patterns(available_of(letters_of("JAMJOB")), state, false);
+        assert.deepEqual(analysis.patterns, []);
+    });
+
+    it("flags two Js when only one word with a J fits", async () => {
+        const analysis = await detect_dead_end_patterns(available_of(letters_of("JAMJ")), state, false);
+        assert.deepEqual(analysis.patterns.map(pattern => pattern.letters), ["JJ"]);
+        assert.deepEqual(analysis.suggested_dumps, ["J"]);
+    });
+
+    it("counts Y as a vowel", async () => {
+        assert.deepEqual((await detect_dead_end_patterns(available_of(letters_of("GYM")), state, false)).patterns, []);
+        const analysis = await detect_dead_end_patterns(available_of(letters_of("GMB")), state, false);
+        assert.ok(analysis.patterns.map(pattern => pattern.description).includes("The hand has no vowels, not even a Y"));
+    });
+});
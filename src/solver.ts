@@ -1,5 +1,6 @@
+import { OFFENSIVE_WORDS } from "./offensive_words";
 import { PlaySequence } from "./types";
-import { getRandomInt } from "./utilities";
+import { getRandomInt, seededRandom } from "./utilities";
 
 export interface GameState {
     /**
@@ -40,1003 +41,6118 @@ export interface AppState {
      * Complete Scrabble dictionary
      */
     all_words_long: Array<Uint8Array>,
+    /**
+     * Set of every hashed word in `all_words_short` (see `hash_dictionary`), built once when the dictionary is loaded.
+     * Since every letter on a board comes from the hand, checking words against the whole dictionary is equivalent to checking against only the makeable words;
+     * two-letter and forbidden words are kept off of the board separately (see `check_word_and_crossings` and `state_without_words`). The only difference is
+     * that hashes can collide, so a run of letters that isn't a word is accepted if it shares a hash with any dictionary word (about 3 in a million runs do).
+     */
+    all_words_short_set: Set<number>,
+    /**
+     * How common each word in `all_words_long` is, from 1 (rare) to 5 (very common), parallel to `all_words_long` (see `rank_commonness`); optional since it adds a byte per word
+     */
+    all_words_long_commonness?: Uint8Array,
+    /**
+     * Words on the boards solved earlier this session, oldest first and at most `WORD_HISTORY_LIMIT` of them (see `record_word_history`)
+     */
+    word_history?: string[],
+    /**
+     * The makeable words from the last solve, so that the next solve only has to look for words made makeable by the letters added since (see `makeable_word_indices`)
+     */
+    candidate_cache?: CandidateCache,
     /**
      * The last game state (if `null`, then no previous game has been played)
      */
     last_game: GameState|null
 }
-type comparison_t = "Same"|"SomeLess"|"GreaterByMoreThanOne"|"GreaterByOne";
 /**
- * The return type when a solution is found
+ * Options controlling how the solver searches for a solution
  */
-export type solution_t = {
+export interface SolverConfig {
     /**
-     * The solved board
+     * Whether every solution must be re-verified to have no letters that aren't part of a word of at least two letters ("strict Bananagrams");
+     * if a solution has such a letter, the solver keeps searching. Boards built entirely by the search can't have such a letter, since every
+     * word played must touch a tile already on the board, so this only matters when building off of a board the user laid out (see `play_from_partial`)
      */
-    board: string[][],
+    strict?: boolean,
     /**
-     * How long it took to solve the board in milliseconds
+     * Whether to follow the variant rule that tiles of two words may only touch diagonally if the words cross each other (see `diagonal_touches`);
+     * if a solution breaks it, the solver keeps searching. Standard Bananagrams allows any diagonal touch.
      */
-    elapsed: number,
+    strict_adjacency?: boolean,
     /**
-     * The state of the game upon solving
+     * The maximum number of words (including incidental crossing words) allowed on the board; unlimited if not given
      */
-    state: GameState
+    max_word_count?: number,
+    /**
+     * The minimum number of columns a solution must span; solutions that are too narrow are rejected and the search continues
+     */
+    min_width?: number,
+    /**
+     * The minimum number of rows a solution must span; solutions that are too short are rejected and the search continues
+     */
+    min_height?: number,
+    /**
+     * The largest bounding-box area (width times height) a solution may have; unlike a maximum width and height, this still allows long, narrow boards as long as they're small overall
+     */
+    max_area?: number,
+    /**
+     * The maximum number of words to try before giving up; unlimited if not given. When this runs out while solving from scratch,
+     * a `SearchTimeout` is returned that can be used to continue the search where it left off.
+     */
+    max_words_to_check?: number,
+    /**
+     * Whether to record the furthest board reached from each opening word tried when solving from scratch (see `solution_t.opening_boards`); this is slow, and only meant for visualizing the search
+     */
+    collect_opening_boards?: boolean,
+    /**
+     * Whether to also report the solution's bounds relative to the first letter of the opening word (see `solution_t.relative_bounds`), e.g. to replay `GameState.play_sequence` from a known origin
+     */
+    relative_to_opening?: boolean,
+    /**
+     * Whether the worker should post the board that leaves the fewest letters in the hand (see `BestBoard`) whenever it improves,
+     * at most once every `STREAM_INTERVAL_MS`, so that the frontend can show it and let the user settle for it rather than waiting
+     */
+    stream_best_boards?: boolean,
+    /**
+     * Roughly how many bytes the solver's optional large allocations (see `memory_stats`) may use; unlimited if not given.
+     * Near the limit, cached indices are dropped and rebuilt only as needed, and opening boards stop being collected, rather than running out of memory.
+     */
+    max_memory_bytes?: number,
+    /**
+     * The order in which words are tried; defaults to `"dictionary"`
+     */
+    word_sort?: word_sort_t,
+    /**
+     * How many letters of the opening word each later word may play through beyond the letters left in the hand; words needing more aren't tried.
+     * Defaults to `DEFAULT_FILTER_LETTERS_ON_BOARD`
+     */
+    filter_letters_on_board?: number,
+    /**
+     * Whether to move words closer to the middle of the board after solving from scratch, to make the solution more compact
+     */
+    optimize_compactness?: boolean,
+    /**
+     * A word that should stay on the board when re-solving. Building off of the previous board always keeps its words; when solving from scratch, this word is tried as the opening word first,
+     * and it's never moved when optimizing compactness. If no solution keeps it, the hand is solved without it (see `solution_t.locked_word_kept`).
+     */
+    locked_word?: string,
+    /**
+     * How to choose the opening word when solving from scratch; defaults to `"auto"`
+     */
+    strategy?: strategy_t,
+    /**
+     * The maximum number of words to try with each opening word when using the `"longest_word"` strategy before moving on to the next; unlimited if not given
+     */
+    anchor_budget?: number,
+    /**
+     * The maximum number of words to try with each opening word before moving on to the next, whatever the strategy, so that one unlucky opening word can't use up all of `max_words_to_check`;
+     * unlimited if not given. Words tried still count toward `max_words_to_check`.
+     */
+    max_words_per_opening?: number,
+    /**
+     * How to rank solutions against each other (see `rank_solutions`); defaults to `DENSITY_RANKING`
+     */
+    ranking?: SolutionRanking,
+    /**
+     * Whether to solve hands of more than `CHUNK_SIZE` tiles a chunk at a time, building each chunk off of the board from the previous one;
+     * this gives up on finding the best board, but can find some board for hands too large to search in full
+     */
+    chunked?: boolean,
+    /**
+     * Whether to polish solutions found from scratch by moving words wherever they most improve the board's ranking (see `ranking`)
+     */
+    polish?: boolean,
+    /**
+     * The maximum number of words to move when polishing; defaults to `POLISH_ROUNDS`
+     */
+    polish_rounds?: number,
+    /**
+     * The minimum number of letters each word played after the opening word must share with the tiles already on the board; see `intersection_mode` for how it's enforced.
+     * Since a word can only share one letter with each word played across it, above one this only asks for as many as there are of those (see `required_intersections`)
+     */
+    min_intersections_per_word?: number,
+    /**
+     * Whether `min_intersections_per_word` rejects placements that share too few letters (`"hard"`, which can make hands unsolvable),
+     * or only tries placements that meet it first (`"soft"`); defaults to `"soft"`
+     */
+    intersection_mode?: constraint_mode_t,
+    /**
+     * The minimum fraction of tiles that must be part of both a horizontal and a vertical word; defaults to 0. Boards are necessarily sparse while they're
+     * being built (the opening word alone has none), so after each placement this only rejects boards that can't reach it with the tiles left (see
+     * `is_acceptable_placement`). Around 0.3 gives deeply interconnected boards rather than wide flat ones.
+     */
+    min_intersection_density?: number,
+    /**
+     * Whether to try playing words in the direction that brings the board closer to square first (vertically when it's wider than it is tall, and vice versa),
+     * rather than alternating directions; this only changes the order of the search
+     */
+    prefer_square?: boolean,
+    /**
+     * Whether to reject every two-letter word, including incidental ones formed by adjacent tiles; this makes many hands much harder to solve
+     */
+    no_two_letter_words?: boolean,
+    /**
+     * Whether every word on the board must be different, including incidental ones; sometimes repeating a word is the only way to solve a hand
+     */
+    no_duplicate_words?: boolean,
+    /**
+     * The maximum number of times the search may back away from each word at a depth before the word is no longer tried at that depth; unlimited if not given.
+     * The counts start over for each opening word. This stops a single hard-to-place word from using up the whole budget.
+     */
+    max_backtracks_per_word?: number,
+    /**
+     * Whether to avoid words in `AppState.word_history` so that each round looks different; they're only played if no solution is found without them.
+     * Only applies when solving from scratch.
+     */
+    avoid_repeats_from_history?: boolean,
+    /**
+     * Letters that must be on the solved board, repeated for each copy required (e.g. `"QZZ"` for a Q and two Zs). The rest of the hand is optional:
+     * if the whole hand can't be solved, the largest part of it that includes these letters is played instead (see `unused_letters` for what's left out).
+     * Always solves from scratch, and can't be continued after running out of budget.
+     */
+    required_letters?: string,
+    /**
+     * Whether to keep the words in `OFFENSIVE_WORDS` off of the board, both as played words and as incidental crossing words
+     */
+    family_friendly?: boolean,
+    /**
+     * Words to keep off of the board for this solve only, both as played words and as incidental crossing words (e.g. words already used by a student);
+     * these are in addition to the words kept off by `family_friendly`
+     */
+    forbidden_words?: string[],
+    /**
+     * Whether to try each word's valid placements from the one that grows the board's bounding box least to most, breaking ties by how many words could still be played after each;
+     * this only changes the order of the search, but roughly doubles the work for tied placements
+     */
+    lookahead_tiebreak?: boolean,
+    /**
+     * Words to try before any others, in order (e.g. words the user would like to see on the board); words that can't be made from the hand are ignored.
+     * The `"longest_word"` strategy still tries the longest words first.
+     */
+    word_priorities?: string[],
+    /**
+     * Whether to give up on boards with at least `VOWEL_PRUNE_MIN_TILES` letters left that are all vowels (or all consonants) when none of the words being tried are;
+     * this speeds up hands short on vowels, but since such letters can sometimes be played through tiles already on the board, it can rarely miss a solution (see `SolverStats.vowel_prunes`)
+     */
+    vowel_prune?: boolean,
+    /**
+     * Whether to keep searching boards with a letter left that none of the words being tried contain (see `SolverStats.pruned_subtrees`);
+     * such boards can never be finished, so this only makes the search slower, but it's useful for checking that the prune doesn't lose solutions
+     */
+    no_coverage_prune?: boolean
 }
-
 /**
- * Value of an empty cell on the board
+ * How a constraint on the search is enforced: `"hard"` rejects anything that breaks it, while `"soft"` only tries what meets it first
  */
-const EMPTY_VALUE = 30;
+export type constraint_mode_t = "hard"|"soft";
 /**
- * Number rows/columns in the board
+ * Fewest letters that must be left, all vowels or all consonants, for `SolverConfig.vowel_prune` to give up on a board
  */
-const BOARD_SIZE = 144;
+const VOWEL_PRUNE_MIN_TILES = 6;
 /**
- * All uppercase letters in the Latin alphabet
+ * Default maximum number of words to move when polishing a solution (see `SolverConfig.polish`)
  */
-const UPPERCASE = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const POLISH_ROUNDS = 50;
 /**
- * The number of each letter present in regular Bananagrams
+ * How the solver chooses the opening word: `"default"` tries the words in order (see `SolverConfig.word_sort`), `"auto"` does the same but picks the order based on the hand when `SolverConfig.word_sort` isn't given
+ * (see `resolve_strategy`), while `"longest_word"` tries the longest makeable words first, each for a share of the budget (see `SolverConfig.anchor_budget`), so that the board shows off the longest word possible
  */
-const REGULAR_TILES = [13, 3, 3, 6, 18, 3, 4, 3, 12, 2, 2, 5, 3, 8, 11, 3, 2, 9, 6, 9, 6, 3, 3, 2, 3, 2];
-
+export type strategy_t = "auto"|"default"|"longest_word";
 /**
- * Hashes a vector of numbers
- * @param v Array to hash
- * @returns The hash of `v`
+ * Order in which the solver tries words: `"dictionary"` keeps the dictionary's order (longest words first), while `"length_asc"` tries the shortest words first,
+ * which can find solutions faster for hands with many uncommon letters by building outward from small words
  */
-function vec_hasher(v: Uint8Array|number[]) {
-    let seed = v.length;
-    v.forEach(num => {
-        let x = ((num >> 16) ^ num) * 0x45d9f3b;
-        let y = ((x >> 16) ^ x) * 0x45d9f3b;
-        let z = (y >> 16) ^ y;
-        seed ^= z + 0x9e3779b9 + (seed << 6) + (seed >> 2);
-    });
-    return seed;
+export type word_sort_t = "dictionary"|"length_asc";
+/**
+ * The furthest board reached from a single opening word
+ */
+export interface OpeningBoard {
+    /**
+     * The opening word
+     */
+    word: string,
+    /**
+     * The board with the fewest letters left in the hand reached from the opening word (see `board_to_vec`)
+     */
+    board: string[][],
+    /**
+     * How many letters were left in the hand for `board`
+     */
+    leftover_letters: number
 }
-
 /**
- * Checks whether two arrays are equal
- * @param arr1 First array to check
- * @param arr2 Seconds array to check
- * @returns Whether the two arrays are equal at all elements
+ * The best partial board found so far by a search, posted by the worker as `{best_board: BestBoard}` (see `SolverConfig.stream_best_boards`)
  */
-function array_equal(arr1: Uint8Array, arr2: Uint8Array) {
-    if (arr1.length !== arr2.length) {
-        return false;
-    }
-    for (let i=0; i<arr1.length; i++) {
-        if (arr1[i] !== arr2[i]) {
-            return false;
-        }
-    }
-    return true;
+export interface BestBoard {
+    /**
+     * The board, cropped to its bounds (see `board_to_vec`)
+     */
+    board: string[][],
+    /**
+     * How many letters were left in the hand for `board`
+     */
+    leftover_letters: number,
+    /**
+     * How many words had been tried when `board` was reached
+     */
+    words_checked: number,
+    /**
+     * The state of the game at `board`, with the whole hand as its letters, so that the board can be kept as the result (see `best_board_solution`)
+     */
+    state: GameState
 }
-
 /**
- * A thin wrapper around the board
+ * Where a search from scratch left off, so that it can be continued
  */
-class Board {
+export interface SearchState {
     /**
-     * The underlying board array
+     * Index of the opening word (among the words makeable from `letters`) that was being tried; continuing restarts from this opening word
      */
-    arr: Uint8Array
+    opening_index: number,
     /**
-     * Creates a new board of size `BOARD_SIZE`x`BOARD_SIZE` filled with `EMPTY_VALUE`
+     * Total words tried across every call so far
      */
-    constructor() {
-        this.arr = new Uint8Array(BOARD_SIZE*BOARD_SIZE);
-        this.arr.fill(EMPTY_VALUE);
-    }
+    words_checked: number,
     /**
-     * Gets a value at the given index in the board
-     * @param row Row of the value to get
-     * @param col Column of the value to get
-     * @returns The value at `(row, col)`
+     * The fewest letters left in the hand by any partial board reached so far
      */
-    get_val(row: number, col: number) {
-        return this.arr[row*BOARD_SIZE + col];
-    }
+    best_leftover_letters: number,
     /**
-     * Sets a value at the given index in the board
-     * @param row Row of the value to set
-     * @param col Column of the value to set
-     * @param val The value to set at `(row, col)`
+     * The hand being solved
      */
-    set_val(row: number, col: number, val: number) {
-        this.arr[row*BOARD_SIZE + col] = val;
-    }
+    letters: Uint8Array
 }
-
 /**
- * Converts a word into a numeric vector representation
- * @param word String word to convert
- * @returns Numeric representation of `word`, with each letter converted from 0 ('A') to 25 ('Z')
- * @see convert_array_to_word - the inverse function
+ * The return type when the solver ran out of words to check before finding a solution
  */
-export function convert_word_to_array(word: string) {
-    const word_arr: number[] = [];
-    for (const char of word) {
-        if (UPPERCASE.includes(char)) {
-            word_arr.push(char.charCodeAt(0) - 65);
-        }
-    }
-    return Uint8Array.from(word_arr);
+export interface SearchTimeout {
+    /**
+     * Human-readable description of how far the search got
+     */
+    message: string,
+    /**
+     * Pass this back to the solver (along with a new `max_words_to_check`) to continue the search
+     */
+    search_state: SearchState
 }
-
+export type comparison_t = "Same"|"SomeLess"|"GreaterByMoreThanOne"|"GreaterByOne";
 /**
- * Converts a numeric vector representation into a string
- * @param word Numeric vector of the word
- * @returns `arr` converted into a string, with each number converted from 'A' (0) to 'Z' (25)
- * @see convert_word_to_array - the inverse function
+ * Details of why building off of the previous board failed, so that the frontend knows how close it came
  */
-function convert_array_to_word(arr: Uint8Array) {
-    const chars: string[] = [];
-    arr.forEach(val => {
-        chars.push(String.fromCharCode(val+65));
-    });
-    return chars.join("");
+export interface ExistingFailure {
+    /**
+     * How the new hand compared to the hand used for the previous board
+     */
+    comparison: comparison_t,
+    /**
+     * Whether playing the single new letter directly on the previous board was attempted
+     */
+    single_letter_attempted: boolean,
+    /**
+     * How many words were tried while building off of the previous board
+     */
+    words_checked: number,
+    /**
+     * The fewest letters left in the hand by any partial board reached while building off of the previous board
+     */
+    best_leftover_letters: number
 }
-
 /**
- * Gets which indices overlap between `previous_play_sequence` and `new_play_sequence`
- * @param previous_play_sequence The play sequence the last time the board was played
- * @param new_play_sequence The new play sequence whose overlap is being compared with `previous_play_sequence`
- * @returns Set of the indices in the board where each overlapping letter between the two sequences was played; may be empty
+ * How many tiles on a board have each pattern of occupied neighbors (tiles with two opposite neighbors, i.e. in the middle of a single word, aren't counted)
  */
-function get_previous_idxs(previous_play_sequence?: PlaySequence, new_play_sequence?: PlaySequence) {
-    if (previous_play_sequence == null || new_play_sequence == null) {
-        return new Set<number>();
-    }
-    else {
-        const previous_idxs: Array<[number, number]> = [];
-        for (let i=0; i<Math.min(previous_play_sequence.length, new_play_sequence.length); i++) {
-            if (array_equal(previous_play_sequence[i][0], new_play_sequence[i][0]) && previous_play_sequence[i][1][0] === new_play_sequence[i][1][0] && previous_play_sequence[i][1][1] === new_play_sequence[i][1][1] && previous_play_sequence[i][1][2] === new_play_sequence[i][1][2]) {
-                const word_len = previous_play_sequence[i][0].length;
-                const start_row = previous_play_sequence[i][1][0];
-                const start_col = previous_play_sequence[i][1][1];
-                if (previous_play_sequence[i][1][2] === "horizontal") {
-                    for (let j=0; j<word_len; j++) {
-                        previous_idxs.push([start_row, start_col+j]);
-                    }
-                }
-                else {
-                    for (let j=0; j<word_len; j++) {
-                        previous_idxs.push([start_row+j, start_col]);
-                    }
-                }
-            }
-        }
-        return new Set(previous_idxs.map(vec_hasher));
-    }
+export interface TopologyStats {
+    /**
+     * Tiles with one occupied neighbor, i.e. at the end of a word
+     */
+    endpoints: number,
+    /**
+     * Tiles with two perpendicular occupied neighbors, i.e. where two words meet at their ends
+     */
+    corners: number,
+    /**
+     * Tiles with three occupied neighbors, i.e. where a word ends against the middle of another
+     */
+    t_junctions: number,
+    /**
+     * Tiles with four occupied neighbors, i.e. where two words cross through each other's middles
+     */
+    crossings: number
 }
-
 /**
- * Converts a `board` to a vector of vectors of chars
- * @param board Board to display
- * @param min_col Minimum occupied column index
- * @param max_col Maximum occupied column index
- * @param min_row Minimum occupied row index
- * @param max_row Maximum occupied row index
- * @returns `board` in vector form (with all numbers converted to letters)
+ * How the mix of letters on a board compares to English
  */
-function board_to_vec(board: Board, min_col: number, max_col: number, min_row: number, max_row: number, previous_idxs: Set<number>) {
-    const board_vec: string[][] = [];
-    for (let row=min_row; row<max_row+1; row++) {
-        const row_vec: string[] = [];
-        for (let col=min_col; col<max_col+1; col++) {
-            if (board.get_val(row, col) == EMPTY_VALUE) {
-                row_vec.push(" ");
-            }
-            else {
-                const c = String.fromCharCode(board.get_val(row, col) + 65);
-                if (!previous_idxs.has(vec_hasher([row, col]))) {
-                    row_vec.push(c);
+export interface LetterBalance {
+    /**
+     * Fraction of the tiles that are vowels (not including 'Y')
+     */
+    vowel_fraction: number,
+    /**
+     * Fraction of the tiles that are consonants (including 'Y')
+     */
+    consonant_fraction: number,
+    /**
+     * Euclidean distance between the fraction of the tiles that are each letter and the same for typical English text; higher values look more unusual
+     */
+    deviation_from_english: number
+}
+/**
+ * How the tiles on a board are spread between its quadrants, split at the average position of the tiles; tiles level with the center count as south and/or east
+ */
+export interface QuadrantStats {
+    /**
+     * Number of tiles north-west of the center
+     */
+    nw: number,
+    /**
+     * Number of tiles north-east of the center
+     */
+    ne: number,
+    /**
+     * Number of tiles south-west of the center
+     */
+    sw: number,
+    /**
+     * Number of tiles south-east of the center
+     */
+    se: number,
+    /**
+     * Standard deviation of the four counts; lower values mean the tiles are spread more evenly
+     */
+    std_dev: number
+}
+/**
+ * Statistics about a search and the solution it found
+ */
+export interface SolverStats {
+    /**
+     * How many words were tried during the search
+     */
+    words_checked: number,
+    /**
+     * Whether every word on the board is different from every other
+     */
+    board_words_unique: boolean,
+    /**
+     * How many words were moved by the compactness optimization (see `SolverConfig.optimize_compactness`)
+     */
+    compactness_optimization_rounds: number,
+    /**
+     * Scrabble score of the board (see `score_board`)
+     */
+    score: number,
+    /**
+     * The order in which words were tried; chosen based on the hand unless `SolverConfig.word_sort` or `SolverConfig.strategy` was given, so this shows what the automatic choice was
+     */
+    word_sort: word_sort_t,
+    /**
+     * How many words were moved when polishing (see `SolverConfig.polish`)
+     */
+    polish_iterations: number,
+    /**
+     * How much polishing raised the board's combined score under `SolverConfig.ranking`
+     */
+    polish_score_delta: number,
+    /**
+     * The shape of the board (see `analyze_board_topology`)
+     */
+    topology: TopologyStats,
+    /**
+     * The mix of letters on the board (see `compute_letter_balance`)
+     */
+    letter_balance: LetterBalance,
+    /**
+     * Whether the makeable words were built from `AppState.candidate_cache` rather than from the whole dictionary
+     */
+    candidate_cache_hit: boolean,
+    /**
+     * How unevenly the tiles are spread between the board's quadrants (see `board_quadrant_stats`)
+     */
+    quadrant_balance_std_dev: number,
+    /**
+     * How many partial boards the search gave up on early because a letter left in the hand couldn't be played in any word; only counted when solving from scratch
+     */
+    pruned_subtrees: number,
+    /**
+     * How many partial boards `SolverConfig.vowel_prune` gave up on; if a hand fails with this above 0, it may be worth trying again without it. Only counted when solving from scratch
+     */
+    vowel_prunes: number,
+    /**
+     * How much of `SolverConfig.max_words_to_check` was left when the board was found, from 0 (found right at the limit) to 1 (found early, or no limit was given)
+     */
+    confidence: number,
+    /**
+     * The mean row and mean column of the tiles (see `board_centroid`)
+     */
+    centroid: [number, number],
+    /**
+     * Whether words were moved around after solving (see `SolverConfig.optimize_compactness` and `SolverConfig.polish`)
+     */
+    used_removal_strategy: boolean
+}
+/**
+ * Measures of the quality of a solved board
+ */
+export interface SolutionScore {
+    /**
+     * Number of words on the board, including incidental crossing words
+     */
+    word_count: number,
+    /**
+     * Number of tiles on the board
+     */
+    tile_count: number,
+    /**
+     * Area of the smallest rectangle containing every tile
+     */
+    bounding_area: number,
+    /**
+     * Scrabble score of the board (see `score_board`)
+     */
+    letter_score: number,
+    /**
+     * Number of tiles that are part of both a horizontal and a vertical word
+     */
+    intersection_count: number,
+    /**
+     * Fraction of the bounding rectangle that is filled with tiles
+     */
+    density: number
+}
+/**
+ * Weights used to combine the dimensions of a `SolutionScore` into a single number, in the order `word_count`, `tile_count`, `bounding_area`, `letter_score`, `intersection_count`, and `density`;
+ * solutions with a higher combined score rank first
+ */
+export interface SolutionRanking {
+    /**
+     * Weight of each dimension; negative weights prefer smaller values
+     */
+    weights: [number, number, number, number, number, number]
+}
+/**
+ * Ranking that prefers the most densely packed boards
+ */
+export const DENSITY_RANKING: SolutionRanking = {weights: [0, 0, 0, 0, 0, 1]};
+/**
+ * Ranking that prefers boards with the fewest words
+ */
+export const FEWEST_WORDS_RANKING: SolutionRanking = {weights: [-1, 0, 0, 0, 0, 0]};
+/**
+ * The return type when a solution is found
+ */
+export type solution_t = {
+    /**
+     * The solved board
+     */
+    board: string[][],
+    /**
+     * How long it took to solve the board in milliseconds
+     */
+    elapsed: number,
+    /**
+     * Measures of the quality of the board
+     */
+    quality: SolutionScore,
+    /**
+     * The state of the game upon solving
+     */
+    state: GameState,
+    /**
+     * Statistics about the search and the solution
+     */
+    stats: SolverStats,
+    /**
+     * If building off of the previous board was tried but failed (so the board was solved from scratch), why it failed
+     */
+    existing_failure?: ExistingFailure,
+    /**
+     * The furthest board reached from each opening word tried, in order; only present if `SolverConfig.collect_opening_boards` was set
+     */
+    opening_boards?: OpeningBoard[],
+    /**
+     * Whether `SolverConfig.locked_word` is on the solved board; only present if a locked word was given
+     */
+    locked_word_kept?: boolean,
+    /**
+     * The opening word the board was built around; only present if `SolverConfig.strategy` was `"longest_word"`
+     */
+    showcased_word?: string,
+    /**
+     * Row and column in the board array of the opening word's first letter; only present if `SolverConfig.relative_to_opening` was set
+     */
+    opening_origin?: [number, number],
+    /**
+     * The bounds of the board as offsets from `opening_origin` (so the opening word starts at row 0, column 0); only present if `SolverConfig.relative_to_opening` was set
+     */
+    relative_bounds?: Pick<GameState, "min_col"|"max_col"|"min_row"|"max_row">,
+    /**
+     * Words on the solved board that are also in `AppState.word_history`; only present if `SolverConfig.avoid_repeats_from_history` was set
+     */
+    reused_history_words?: string[],
+    /**
+     * The makeable words for the hand, to pass back in `AppState.candidate_cache` for the next solve
+     */
+    candidate_cache?: CandidateCache
+}
+
+/**
+ * Value of an empty cell on the board
+ */
+const EMPTY_VALUE = 30;
+/**
+ * Number rows/columns in the board
+ */
+export const BOARD_SIZE = 144;
+/**
+ * Longest word that can be played from the middle of the board in either direction without running off the edge
+ */
+const MAX_WORD_LENGTH = BOARD_SIZE/2 - 1;
+/**
+ * All uppercase letters in the Latin alphabet
+ */
+const UPPERCASE = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+/**
+ * The number of each letter present in regular Bananagrams
+ */
+const REGULAR_TILES = [13, 3, 3, 6, 18, 3, 4, 3, 12, 2, 2, 5, 3, 8, 11, 3, 2, 9, 6, 9, 6, 3, 3, 2, 3, 2];
+/**
+ * The value of each letter in Scrabble
+ */
+const LETTER_VALUES = [1, 3, 3, 2, 1, 4, 2, 4, 1, 8, 5, 1, 3, 1, 1, 3, 10, 1, 1, 1, 1, 4, 4, 8, 4, 10];
+/**
+ * The fraction of letters in typical English text that are each letter
+ */
+const ENGLISH_LETTER_FREQUENCIES = [0.08167, 0.01492, 0.02782, 0.04253, 0.12702, 0.02228, 0.02015, 0.06094, 0.06966, 0.00153, 0.00772, 0.04025, 0.02406, 0.06749, 0.07507, 0.01929, 0.00095, 0.05987, 0.06327, 0.09056, 0.02758, 0.00978, 0.0236, 0.0015, 0.01974, 0.00074];
+
+/**
+ * Indices (from 0 for 'A' to 25 for 'Z') of the vowels, not including 'Y'
+ */
+const VOWELS = [0, 4, 8, 14, 20];
+/**
+ * Indices of the letters that are rare enough that holding several of them is usually a problem
+ */
+const RARE_LETTERS = [9, 10, 16, 23, 25];
+
+/**
+ * Hashes a vector of numbers
+ * @param v Array to hash
+ * @returns The hash of `v`
+ */
+function vec_hasher(v: Uint8Array|number[]) {
+    let seed = v.length;
+    v.forEach(num => {
+        let x = ((num >> 16) ^ num) * 0x45d9f3b;
+        let y = ((x >> 16) ^ x) * 0x45d9f3b;
+        let z = (y >> 16) ^ y;
+        seed ^= z + 0x9e3779b9 + (seed << 6) + (seed >> 2);
+    });
+    return seed;
+}
+
+/**
+ * Checks whether two arrays are equal
+ * @param arr1 First array to check
+ * @param arr2 Seconds array to check
+ * @returns Whether the two arrays are equal at all elements
+ */
+function array_equal(arr1: Uint8Array, arr2: Uint8Array) {
+    if (arr1.length !== arr2.length) {
+        return false;
+    }
+    for (let i=0; i<arr1.length; i++) {
+        if (arr1[i] !== arr2[i]) {
+            return false;
+        }
+    }
+    return true;
+}
+
+/**
+ * A thin wrapper around the board
+ */
+export class Board {
+    /**
+     * The underlying board array
+     */
+    arr: Uint8Array
+    /**
+     * Creates a new board of size `BOARD_SIZE`x`BOARD_SIZE` filled with `EMPTY_VALUE`
+     */
+    constructor() {
+        this.arr = new Uint8Array(BOARD_SIZE*BOARD_SIZE);
+        this.arr.fill(EMPTY_VALUE);
+    }
+    /**
+     * Gets a value at the given index in the board
+     * @param row Row of the value to get
+     * @param col Column of the value to get
+     * @returns The value at `(row, col)`
+     */
+    get_val(row: number, col: number) {
+        return this.arr[row*BOARD_SIZE + col];
+    }
+    /**
+     * Sets a value at the given index in the board
+     * @param row Row of the value to set
+     * @param col Column of the value to set
+     * @param val The value to set at `(row, col)`
+     */
+    set_val(row: number, col: number, val: number) {
+        this.arr[row*BOARD_SIZE + col] = val;
+    }
+}
+
+/**
+ * Gets the center of the board array, where solving from scratch starts: the opening word is played across this row, centered on this column
+ * @returns The index of the center row and column
+ */
+export function board_center() {
+    return BOARD_SIZE/2;
+}
+
+/**
+ * How much room a board has left to grow in each direction before the edge of the board array
+ */
+export interface BoardReach {
+    /**
+     * Empty rows above the board
+     */
+    top: number,
+    /**
+     * Empty rows below the board
+     */
+    bottom: number,
+    /**
+     * Empty columns left of the board
+     */
+    left: number,
+    /**
+     * Empty columns right of the board
+     */
+    right: number
+}
+
+/**
+ * Gets how much room a board has left to grow in each direction, so that the frontend doesn't need to know the size of the board array
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @returns The number of empty rows or columns on each side of the bounds
+ */
+export function board_reach(min_col: number, max_col: number, min_row: number, max_row: number): BoardReach {
+    return {top: min_row, bottom: BOARD_SIZE - 1 - max_row, left: min_col, right: BOARD_SIZE - 1 - max_col};
+}
+
+/**
+ * Wraps an existing board array (e.g. `GameState.board`) in a `Board`
+ * @param arr The board array, of length `BOARD_SIZE*BOARD_SIZE`
+ * @returns A `Board` using `arr` (which is not copied)
+ */
+function board_from_array(arr: Uint8Array) {
+    const board = new Board();
+    board.arr = arr;
+    return board;
+}
+
+/**
+ * Converts a word into a numeric vector representation
+ * @param word String word to convert
+ * @returns Numeric representation of `word`, with each letter converted from 0 ('A') to 25 ('Z')
+ * @see convert_array_to_word - the inverse function
+ */
+export function convert_word_to_array(word: string) {
+    const word_arr: number[] = [];
+    for (const char of word) {
+        if (UPPERCASE.includes(char)) {
+            word_arr.push(char.charCodeAt(0) - 65);
+        }
+    }
+    return Uint8Array.from(word_arr);
+}
+
+/**
+ * Converts a whole dictionary into numeric vector representations that all share one contiguous buffer,
+ * which uses far less memory than a separate buffer per word and is much faster to send to a worker
+ * @param words String words to convert
+ * @returns Numeric representation of each word in `words` (see `convert_word_to_array`), each a view into the shared buffer
+ */
+export function convert_words_to_arena(words: string[]) {
+    let total = 0;
+    for (const word of words) {
+        for (const char of word) {
+            if (UPPERCASE.includes(char)) {
+                total += 1;
+            }
+        }
+    }
+    const arena = new Uint8Array(total);
+    const views: Uint8Array[] = [];
+    let offset = 0;
+    for (const word of words) {
+        const start = offset;
+        for (const char of word) {
+            if (UPPERCASE.includes(char)) {
+                arena[offset] = char.charCodeAt(0) - 65;
+                offset += 1;
+            }
+        }
+        views.push(arena.subarray(start, offset));
+    }
+    return views;
+}
+
+/**
+ * Scores a word using the Scrabble letter values (without any bonus squares)
+ * @param word String word to score; characters other than the letters A-Z are ignored
+ * @returns The sum of the values of the letters in `word`
+ */
+export function score_word(word: string) {
+    return convert_word_to_array(word.toUpperCase()).reduce((a, letter) => a + LETTER_VALUES[letter], 0);
+}
+
+/**
+ * Hashes every word in a dictionary for fast membership checking
+ * @param words Array of words (see `convert_word_to_array`)
+ * @returns Set of the hash of each word in `words`
+ */
+export function hash_dictionary(words: Array<Uint8Array>) {
+    return new Set(words.map(vec_hasher));
+}
+
+/**
+ * Converts a numeric vector representation into a string
+ * @param word Numeric vector of the word
+ * @returns `arr` converted into a string, with each number converted from 'A' (0) to 'Z' (25)
+ * @see convert_word_to_array - the inverse function
+ */
+function convert_array_to_word(arr: Uint8Array) {
+    const chars: string[] = [];
+    arr.forEach(val => {
+        chars.push(String.fromCharCode(val+65));
+    });
+    return chars.join("");
+}
+
+/**
+ * Gets which indices overlap between `previous_play_sequence` and `new_play_sequence`
+ * @param previous_play_sequence The play sequence the last time the board was played
+ * @param new_play_sequence The new play sequence whose overlap is being compared with `previous_play_sequence`
+ * @returns Set of the indices in the board where each overlapping letter between the two sequences was played; may be empty
+ */
+function get_previous_idxs(previous_play_sequence?: PlaySequence, new_play_sequence?: PlaySequence) {
+    if (previous_play_sequence == null || new_play_sequence == null) {
+        return new Set<number>();
+    }
+    else {
+        const previous_idxs: Array<[number, number]> = [];
+        for (let i=0; i<Math.min(previous_play_sequence.length, new_play_sequence.length); i++) {
+            if (array_equal(previous_play_sequence[i][0], new_play_sequence[i][0]) && previous_play_sequence[i][1][0] === new_play_sequence[i][1][0] && previous_play_sequence[i][1][1] === new_play_sequence[i][1][1] && previous_play_sequence[i][1][2] === new_play_sequence[i][1][2]) {
+                const word_len = previous_play_sequence[i][0].length;
+                const start_row = previous_play_sequence[i][1][0];
+                const start_col = previous_play_sequence[i][1][1];
+                if (previous_play_sequence[i][1][2] === "horizontal") {
+                    for (let j=0; j<word_len; j++) {
+                        previous_idxs.push([start_row, start_col+j]);
+                    }
+                }
+                else {
+                    for (let j=0; j<word_len; j++) {
+                        previous_idxs.push([start_row+j, start_col]);
+                    }
+                }
+            }
+        }
+        return new Set(previous_idxs.map(vec_hasher));
+    }
+}
+
+/**
+ * Converts a `board` to a vector of vectors of chars
+ * @param board Board to display
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @returns `board` in vector form (with all numbers converted to letters)
+ */
+function board_to_vec(board: Board, min_col: number, max_col: number, min_row: number, max_row: number, previous_idxs: Set<number>) {
+    const board_vec: string[][] = [];
+    for (let row=min_row; row<max_row+1; row++) {
+        const row_vec: string[] = [];
+        for (let col=min_col; col<max_col+1; col++) {
+            if (board.get_val(row, col) == EMPTY_VALUE) {
+                row_vec.push(" ");
+            }
+            else {
+                const c = String.fromCharCode(board.get_val(row, col) + 65);
+                if (!previous_idxs.has(vec_hasher([row, col]))) {
+                    row_vec.push(c);
+                }
+                else {
+                    row_vec.push(c + "*");
+                }
+            }
+        }
+        board_vec.push(row_vec);
+    }
+    return board_vec;
+}
+
+/**
+ * A word (i.e. a run of two or more letters) on the board
+ */
+export interface PlacedWord {
+    /**
+     * The letters of the word
+     */
+    word: string,
+    /**
+     * Row of the word's first letter
+     */
+    row: number,
+    /**
+     * Column of the word's first letter
+     */
+    col: number,
+    /**
+     * Direction the word reads in
+     */
+    direction: "horizontal"|"vertical"
+}
+
+/**
+ * Extracts every word on the board, i.e. every horizontal and vertical run of two or more letters (without checking them against any dictionary)
+ * @param board `Board` to extract from
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @returns Every horizontal word from top to bottom, followed by every vertical word from left to right
+ */
+function extract_placed_words(board: Board, min_col: number, max_col: number, min_row: number, max_row: number) {
+    const words: PlacedWord[] = [];
+    for (let row=min_row; row<max_row+1; row++) {
+        let start = -1;
+        for (let col=min_col; col<max_col+2; col++) {
+            const occupied = col <= max_col && board.get_val(row, col) != EMPTY_VALUE;
+            if (occupied && start === -1) {
+                start = col;
+            }
+            else if (!occupied && start !== -1) {
+                if (col - start > 1) {
+                    words.push({word: convert_array_to_word(board.arr.slice(row*BOARD_SIZE + start, row*BOARD_SIZE + col)), row: row, col: start, direction: "horizontal"});
+                }
+                start = -1;
+            }
+        }
+    }
+    for (let col=min_col; col<max_col+1; col++) {
+        let start = -1;
+        const letters: number[] = [];
+        for (let row=min_row; row<max_row+2; row++) {
+            const occupied = row <= max_row && board.get_val(row, col) != EMPTY_VALUE;
+            if (occupied) {
+                if (start === -1) {
+                    start = row;
+                }
+                letters.push(board.get_val(row, col));
+            }
+            else if (start !== -1) {
+                if (letters.length > 1) {
+                    words.push({word: convert_array_to_word(Uint8Array.from(letters)), row: start, col: col, direction: "vertical"});
+                }
+                start = -1;
+                letters.length = 0;
+            }
+        }
+    }
+    return words;
+}
+
+/**
+ * Extracts every run of two or more letters on the board without checking them against any dictionary, e.g. to show what "words" a user has formed even if they aren't real
+ * @param board The board array (e.g. `GameState.board`)
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @returns Every horizontal run from top to bottom, followed by every vertical run from left to right
+ */
+export function extract_all_letter_sequences(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number) {
+    return extract_placed_words(board_from_array(board), min_col, max_col, min_row, max_row);
+}
+
+/**
+ * A word on a board, split into the tiles that were already on an earlier board and those played from the hand since
+ */
+export interface AttributedWord extends PlacedWord {
+    /**
+     * `[row, col]` of each of the word's tiles that the earlier board already had
+     */
+    reused_cells: Array<[number, number]>,
+    /**
+     * `[row, col]` of each of the word's tiles that was played from the hand
+     */
+    new_cells: Array<[number, number]>,
+    /**
+     * Number of tiles in `new_cells`
+     */
+    hand_letters_used: number,
+    /**
+     * Number of tiles in `reused_cells`
+     */
+    board_letters_reused: number
+}
+
+/**
+ * Extracts every word on a board and works out which of its tiles were already on an earlier board, e.g. to show how a solution grew from the last one
+ * @param board The board array (e.g. `GameState.board`)
+ * @param before The earlier board array, e.g. `AppState.last_game.board` before solving; a tile counts as reused if this has the same letter in the same cell
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @returns Every horizontal word from top to bottom, followed by every vertical word from left to right
+ */
+export function attribute_placed_words(board: Uint8Array, before: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number) {
+    return extract_placed_words(board_from_array(board), min_col, max_col, min_row, max_row).map(placed => {
+        const reused_cells: Array<[number, number]> = [];
+        const new_cells: Array<[number, number]> = [];
+        for (let i=0; i<placed.word.length; i++) {
+            const row = placed.direction === "vertical" ? placed.row + i : placed.row;
+            const col = placed.direction === "horizontal" ? placed.col + i : placed.col;
+            if (before[row*BOARD_SIZE + col] === board[row*BOARD_SIZE + col]) {
+                reused_cells.push([row, col]);
+            }
+            else {
+                new_cells.push([row, col]);
+            }
+        }
+        const attributed: AttributedWord = {...placed, reused_cells: reused_cells, new_cells: new_cells, hand_letters_used: new_cells.length, board_letters_reused: reused_cells.length};
+        return attributed;
+    });
+}
+
+/**
+ * Scores every word on the board like Scrabble, so letters where words cross count once for each word
+ * @param board `Board` to score
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @returns The sum of the scores of every word on the board (see `score_word`)
+ */
+function board_score(board: Board, min_col: number, max_col: number, min_row: number, max_row: number) {
+    return extract_placed_words(board, min_col, max_col, min_row, max_row).reduce((a, placed) => a + score_word(placed.word), 0);
+}
+
+/**
+ * Scores every word on the board like Scrabble, so letters where words cross count once for each word
+ * @param board The board array (e.g. `GameState.board`)
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @returns The sum of the scores of every word on the board (see `score_word`)
+ */
+export function score_board(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number) {
+    return board_score(board_from_array(board), min_col, max_col, min_row, max_row);
+}
+
+/**
+ * Measures the quality of a board
+ * @param board `Board` to measure
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @returns Every dimension of the board's `SolutionScore`
+ */
+function solution_score(board: Board, min_col: number, max_col: number, min_row: number, max_row: number): SolutionScore {
+    const words = extract_placed_words(board, min_col, max_col, min_row, max_row);
+    // Tiles covered by a horizontal word
+    const horizontal_cells = new Set<number>();
+    for (const placed of words) {
+        if (placed.direction === "horizontal") {
+            for (let i=0; i<placed.word.length; i++) {
+                horizontal_cells.add(vec_hasher([placed.row, placed.col+i]));
+            }
+        }
+    }
+    let intersection_count = 0;
+    for (const placed of words) {
+        if (placed.direction === "vertical") {
+            for (let i=0; i<placed.word.length; i++) {
+                if (horizontal_cells.has(vec_hasher([placed.row+i, placed.col]))) {
+                    intersection_count += 1;
+                }
+            }
+        }
+    }
+    let tile_count = 0;
+    for (let row=min_row; row<max_row+1; row++) {
+        for (let col=min_col; col<max_col+1; col++) {
+            if (board.get_val(row, col) != EMPTY_VALUE) {
+                tile_count += 1;
+            }
+        }
+    }
+    const bounding_area = (max_col - min_col + 1) * (max_row - min_row + 1);
+    return {
+        word_count: words.length,
+        tile_count: tile_count,
+        bounding_area: bounding_area,
+        letter_score: words.reduce((a, placed) => a + score_word(placed.word), 0),
+        intersection_count: intersection_count,
+        density: tile_count/bounding_area
+    };
+}
+
+/**
+ * Combines the dimensions of a `SolutionScore` into a single number
+ * @param score Score to combine
+ * @param ranking Weight of each dimension
+ * @returns The weighted sum of the dimensions
+ */
+function combined_score(score: SolutionScore, ranking: SolutionRanking) {
+    const dimensions = [score.word_count, score.tile_count, score.bounding_area, score.letter_score, score.intersection_count, score.density];
+    return dimensions.reduce((a, dimension, i) => a + dimension*ranking.weights[i], 0);
+}
+
+/**
+ * Orders solutions from best to worst
+ * @param solutions Solutions to order (not modified)
+ * @param ranking How to weigh each dimension of the solutions' quality
+ * @returns A new array of the solutions, with the highest combined score first
+ */
+export function rank_solutions(solutions: solution_t[], ranking: SolutionRanking = DENSITY_RANKING) {
+    return [...solutions].sort((a, b) => combined_score(b.quality, ranking) - combined_score(a.quality, ranking));
+}
+
+/**
+ * The crossing structure of the words on a board
+ */
+export interface WordGraph {
+    /**
+     * Every word on the board (see `extract_all_letter_sequences`)
+     */
+    nodes: PlacedWord[],
+    /**
+     * Pairs of indices into `nodes` of words that share a tile
+     */
+    edges: Array<[number, number]>
+}
+
+/**
+ * Finds which perpendicular words share a tile
+ * @param nodes Words on a board (see `extract_placed_words`)
+ * @returns Pairs of indices into `nodes` of words that share a tile, horizontal word first
+ */
+function crossing_edges(nodes: PlacedWord[]) {
+    const edges: Array<[number, number]> = [];
+    for (let i=0; i<nodes.length; i++) {
+        const across = nodes[i];
+        if (across.direction !== "horizontal") {
+            continue;
+        }
+        for (let j=0; j<nodes.length; j++) {
+            const down = nodes[j];
+            // Perpendicular words share a tile if each passes through the other's row/column
+            if (down.direction === "vertical" && down.col >= across.col && down.col < across.col + across.word.length && across.row >= down.row && across.row < down.row + down.word.length) {
+                edges.push([i, j]);
+            }
+        }
+    }
+    return edges;
+}
+
+/**
+ * Gets which words on a board cross which, e.g. for drawing a "web" of the board or finding poorly-connected layouts
+ * @param board The board array (e.g. `GameState.board`)
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @returns The words on the board and the pairs of them that cross
+ */
+export function word_graph(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number): WordGraph {
+    const nodes = extract_placed_words(board_from_array(board), min_col, max_col, min_row, max_row);
+    return {nodes: nodes, edges: crossing_edges(nodes)};
+}
+
+/**
+ * Writes the crossing structure of the words on a board as a Graphviz DOT graph, e.g. to paste into an online Graphviz viewer
+ * @param board The board array (e.g. `GameState.board`)
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @param valid_words_set Set of hashed valid words (e.g. `AppState.all_words_short_set`); runs of letters that aren't valid words are left out of the graph
+ * @returns An undirected DOT graph with a node for each word and an edge, labeled with the shared letter, between each pair of words that share a tile
+ */
+export function board_to_dot(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number, valid_words_set: Set<number>) {
+    const nodes = extract_placed_words(board_from_array(board), min_col, max_col, min_row, max_row).filter(placed => valid_words_set.has(vec_hasher(convert_word_to_array(placed.word))));
+    const lines = ["graph board {"];
+    nodes.forEach((placed, i) => lines.push(`    w${i} [label="${placed.word}"];`));
+    for (const [across, down] of crossing_edges(nodes)) {
+        const shared = nodes[across].word[nodes[down].col - nodes[across].col];
+        lines.push(`    w${across} -- w${down} [label="${shared}"];`);
+    }
+    lines.push("}");
+    return lines.join("\n");
+}
+
+/**
+ * Checks whether any word appears on the board more than once
+ * @param board `Board` to check
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @returns Whether every horizontal and vertical word on the board is distinct
+ */
+function all_words_unique_on_board(board: Board, min_col: number, max_col: number, min_row: number, max_row: number) {
+    const seen = new Set<string>();
+    for (const placed of extract_placed_words(board, min_col, max_col, min_row, max_row)) {
+        if (seen.has(placed.word)) {
+            return false;
+        }
+        seen.add(placed.word);
+    }
+    return true;
+}
+
+/**
+ * Checks whether any word appears on the board more than once
+ * @param board The board array (e.g. `GameState.board`)
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @returns Whether every horizontal and vertical word on the board is distinct
+ */
+export function all_words_unique(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number) {
+    return all_words_unique_on_board(board_from_array(board), min_col, max_col, min_row, max_row);
+}
+
+/**
+ * Counts of the words on a board
+ */
+export interface BoardWordCount {
+    /**
+     * Total number of words
+     */
+    total: number,
+    /**
+     * Number of horizontal words
+     */
+    horizontal: number,
+    /**
+     * Number of vertical words
+     */
+    vertical: number,
+    /**
+     * Average length of the words (0 if there are none)
+     */
+    avg_length: number,
+    /**
+     * Length of the longest word (0 if there are none)
+     */
+    max_length: number
+}
+
+/**
+ * Counts the words on a board, split by direction
+ * @param board The board array (e.g. `GameState.board`)
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @returns The number of words in total and in each direction, along with their average and maximum length
+ */
+export function count_words_on_board(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number): BoardWordCount {
+    const words = extract_placed_words(board_from_array(board), min_col, max_col, min_row, max_row);
+    const horizontal = words.filter(placed => placed.direction === "horizontal").length;
+    const total_length = words.reduce((a, placed) => a + placed.word.length, 0);
+    return {
+        total: words.length,
+        horizontal: horizontal,
+        vertical: words.length - horizontal,
+        avg_length: words.length === 0 ? 0 : total_length/words.length,
+        max_length: words.reduce((a, placed) => Math.max(a, placed.word.length), 0)
+    };
+}
+
+/**
+ * Gets the fraction of the words on a board that are exactly two letters long; boards with lots of two-letter crossings look cluttered, so lower is cleaner
+ * @param board The board array (e.g. `GameState.board`)
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @returns The fraction of two-letter words, between 0 and 1 (0 if there are no words)
+ */
+export function two_letter_ratio(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number) {
+    const words = extract_placed_words(board_from_array(board), min_col, max_col, min_row, max_row);
+    return words.length === 0 ? 0 : words.filter(placed => placed.word.length === 2).length/words.length;
+}
+
+/**
+ * A single occupied cell of the board, as given by `board_to_cell_map`
+ */
+export interface CellInfo {
+    /**
+     * The letter in the cell
+     */
+    letter: string,
+    /**
+     * Whether the letter was newly played
+     */
+    is_new: boolean
+}
+
+/**
+ * Converts a board into a mapping of coordinates to cells, for constant-time lookup by coordinate
+ * @param board The board array (e.g. `GameState.board`)
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @param new_rows Rows of the newly played letters (paired with `new_cols`)
+ * @param new_cols Columns of the newly played letters (paired with `new_rows`)
+ * @returns Map from `"row,col"` of every occupied cell to its letter and whether it's new
+ */
+export function board_to_cell_map(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number, new_rows: number[], new_cols: number[]) {
+    const new_idxs = new Set<number>();
+    for (let i=0; i<Math.min(new_rows.length, new_cols.length); i++) {
+        new_idxs.add(vec_hasher([new_rows[i], new_cols[i]]));
+    }
+    const cells = new Map<string, CellInfo>();
+    for (let row=min_row; row<max_row+1; row++) {
+        for (let col=min_col; col<max_col+1; col++) {
+            const val = board[row*BOARD_SIZE + col];
+            if (val != EMPTY_VALUE) {
+                cells.set(row + "," + col, {letter: String.fromCharCode(val + 65), is_new: new_idxs.has(vec_hasher([row, col]))});
+            }
+        }
+    }
+    return cells;
+}
+
+/**
+ * Converts a board into a stable string that doesn't depend on where the board is or how it's turned, e.g. for comparing boards or snapshot testing
+ * 
+ * The board is translated to the origin, and of its eight rotations and reflections the one with the lexicographically smallest text is used,
+ * so any two boards that are the same up to symmetry give the same string.
+ * @param board The board array (e.g. `GameState.board`)
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @returns The rows of the board joined by newlines, with a space for each empty cell
+ */
+export function canonical_board(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number) {
+    const height = max_row - min_row + 1;
+    const width = max_col - min_col + 1;
+    const cell = (row: number, col: number) => {
+        const val = board[(min_row+row)*BOARD_SIZE + min_col+col];
+        return val == EMPTY_VALUE ? " " : String.fromCharCode(val + 65);
+    };
+    // Each transform maps a cell of the output (with the given number of rows and columns) back to a cell of the board
+    const transforms: Array<[number, number, (r: number, c: number) => string]> = [
+        [height, width, (r, c) => cell(r, c)],
+        [height, width, (r, c) => cell(r, width-1-c)],
+        [height, width, (r, c) => cell(height-1-r, c)],
+        [height, width, (r, c) => cell(height-1-r, width-1-c)],
+        [width, height, (r, c) => cell(c, r)],
+        [width, height, (r, c) => cell(c, width-1-r)],
+        [width, height, (r, c) => cell(height-1-c, r)],
+        [width, height, (r, c) => cell(height-1-c, width-1-r)]
+    ];
+    let best: string|null = null;
+    for (const [rows, cols, get] of transforms) {
+        const lines: string[] = [];
+        for (let r=0; r<rows; r++) {
+            let line = "";
+            for (let c=0; c<cols; c++) {
+                line += get(r, c);
+            }
+            lines.push(line);
+        }
+        const text = lines.join("\n");
+        if (best == null || text < best) {
+            best = text;
+        }
+    }
+    return best!;
+}
+
+/**
+ * Gets which neighbors of each cell of the board are occupied, e.g. for drawing lines between adjacent tiles
+ * @param board The board array (e.g. `GameState.board`)
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @returns Array of rows, each an array of the bitmask of the occupied neighbors of every cell in the row (1 is up, 2 is right, 4 is down, and 8 is left);
+ * empty cells are 0, and cells outside the bounds are treated as empty
+ */
+export function board_to_adjacency_overlay(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number) {
+    const occupied = (row: number, col: number) => row >= min_row && row <= max_row && col >= min_col && col <= max_col && board[row*BOARD_SIZE + col] != EMPTY_VALUE;
+    const overlay: number[][] = [];
+    for (let row=min_row; row<max_row+1; row++) {
+        const overlay_row: number[] = [];
+        for (let col=min_col; col<max_col+1; col++) {
+            if (!occupied(row, col)) {
+                overlay_row.push(0);
+                continue;
+            }
+            overlay_row.push((occupied(row-1, col) ? 1 : 0) | (occupied(row, col+1) ? 2 : 0) | (occupied(row+1, col) ? 4 : 0) | (occupied(row, col-1) ? 8 : 0));
+        }
+        overlay.push(overlay_row);
+    }
+    return overlay;
+}
+
+/**
+ * Gets the occupied cells next to at least one empty cell, which are the only places new words can connect to the board
+ * @param board The board array (e.g. `GameState.board`)
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @returns Array of the `[row, col]` of each such cell, from top to bottom and left to right
+ */
+export function board_perimeter_cells(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number) {
+    const cells: Array<[number, number]> = [];
+    for (let row=min_row; row<max_row+1; row++) {
+        for (let col=min_col; col<max_col+1; col++) {
+            if (board[row*BOARD_SIZE + col] == EMPTY_VALUE) {
+                continue;
+            }
+            // Cells on the edge of the board array have no empty neighbor beyond it
+            const empty_neighbor = (row > 0 && board[(row-1)*BOARD_SIZE + col] == EMPTY_VALUE) || (row < BOARD_SIZE-1 && board[(row+1)*BOARD_SIZE + col] == EMPTY_VALUE)
+                || (col > 0 && board[row*BOARD_SIZE + col-1] == EMPTY_VALUE) || (col < BOARD_SIZE-1 && board[row*BOARD_SIZE + col+1] == EMPTY_VALUE);
+            if (empty_neighbor) {
+                cells.push([row, col]);
+            }
+        }
+    }
+    return cells;
+}
+
+/**
+ * Classifies each tile on the board by its occupied neighbors
+ * @param board `Board` to analyze
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @returns How many tiles are endpoints, corners, T-junctions, and crossings
+ */
+function board_topology(board: Board, min_col: number, max_col: number, min_row: number, max_row: number): TopologyStats {
+    const stats: TopologyStats = {endpoints: 0, corners: 0, t_junctions: 0, crossings: 0};
+    for (let row=min_row; row<max_row+1; row++) {
+        for (let col=min_col; col<max_col+1; col++) {
+            if (board.get_val(row, col) == EMPTY_VALUE) {
+                continue;
+            }
+            const vertical = Number(row > 0 && board.get_val(row-1, col) != EMPTY_VALUE) + Number(row < BOARD_SIZE-1 && board.get_val(row+1, col) != EMPTY_VALUE);
+            const horizontal = Number(col > 0 && board.get_val(row, col-1) != EMPTY_VALUE) + Number(col < BOARD_SIZE-1 && board.get_val(row, col+1) != EMPTY_VALUE);
+            switch (vertical + horizontal) {
+                case 1:
+                    stats.endpoints += 1;
+                    break;
+                case 2:
+                    // Two opposite neighbors is just the middle of a word
+                    if (vertical === 1) {
+                        stats.corners += 1;
+                    }
+                    break;
+                case 3:
+                    stats.t_junctions += 1;
+                    break;
+                case 4:
+                    stats.crossings += 1;
+                    break;
+            }
+        }
+    }
+    return stats;
+}
+
+/**
+ * Counts the endpoints, corners, T-junctions, and crossings on a board, e.g. to compare how tangled different solutions are
+ * @param board The board array (e.g. `GameState.board`)
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @returns How many tiles have each pattern of occupied neighbors
+ */
+export function analyze_board_topology(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number) {
+    return board_topology(board_from_array(board), min_col, max_col, min_row, max_row);
+}
+
+/**
+ * Gets the average position of the tiles on the board
+ * @param board `Board` to analyze
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @returns The mean row and mean column of the tiles, as indices into the whole board array; both 0 for an empty board
+ */
+function centroid(board: Board, min_col: number, max_col: number, min_row: number, max_row: number): [number, number] {
+    let count = 0;
+    let row_sum = 0;
+    let col_sum = 0;
+    for (let row=min_row; row<max_row+1; row++) {
+        for (let col=min_col; col<max_col+1; col++) {
+            if (board.get_val(row, col) != EMPTY_VALUE) {
+                count += 1;
+                row_sum += row;
+                col_sum += col;
+            }
+        }
+    }
+    return count === 0 ? [0, 0] : [row_sum/count, col_sum/count];
+}
+
+/**
+ * Gets the average position of the tiles on a board, e.g. to center a view of the board on its tiles
+ * @param board The board array (e.g. `GameState.board`)
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @returns The mean row and mean column of the tiles, as indices into the whole board array; both 0 for an empty board
+ */
+export function board_centroid(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number) {
+    const [row, col] = centroid(board_from_array(board), min_col, max_col, min_row, max_row);
+    return {row: row, col: col};
+}
+
+/**
+ * Counts the tiles in each quadrant of the board around the tiles' centroid
+ * @param board `Board` to analyze
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @returns The number of tiles in each quadrant and the standard deviation of those numbers; tiles level with the centroid count as south and/or east
+ */
+function quadrant_stats(board: Board, min_col: number, max_col: number, min_row: number, max_row: number): QuadrantStats {
+    const tiles: Array<[number, number]> = [];
+    for (let row=min_row; row<max_row+1; row++) {
+        for (let col=min_col; col<max_col+1; col++) {
+            if (board.get_val(row, col) != EMPTY_VALUE) {
+                tiles.push([row, col]);
+            }
+        }
+    }
+    if (tiles.length === 0) {
+        return {nw: 0, ne: 0, sw: 0, se: 0, std_dev: 0};
+    }
+    const [center_row, center_col] = centroid(board, min_col, max_col, min_row, max_row);
+    const stats = {nw: 0, ne: 0, sw: 0, se: 0, std_dev: 0};
+    for (const [row, col] of tiles) {
+        if (row < center_row && col < center_col) {
+            stats.nw += 1;
+        }
+        else if (row < center_row) {
+            stats.ne += 1;
+        }
+        else if (col < center_col) {
+            stats.sw += 1;
+        }
+        else {
+            stats.se += 1;
+        }
+    }
+    const counts = [stats.nw, stats.ne, stats.sw, stats.se];
+    const mean = tiles.length/4;
+    stats.std_dev = Math.sqrt(counts.reduce((a, count) => a + (count - mean)**2, 0)/4);
+    return stats;
+}
+
+/**
+ * Counts the tiles in each quadrant of a board around the tiles' centroid, e.g. to tell evenly spread boards from clustered ones
+ * @param board The board array (e.g. `GameState.board`)
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @returns The number of tiles in each quadrant and how unevenly they're spread
+ */
+export function board_quadrant_stats(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number) {
+    return quadrant_stats(board_from_array(board), min_col, max_col, min_row, max_row);
+}
+
+/**
+ * Compares the mix of letters on the board to English
+ * @param board `Board` to analyze
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @returns The fractions of vowels and consonants, and how far the letter frequencies are from English; all 0 for an empty board
+ */
+function letter_balance(board: Board, min_col: number, max_col: number, min_row: number, max_row: number): LetterBalance {
+    const counts = count_board_letters(board.arr, min_col, max_col, min_row, max_row);
+    const total = counts.reduce((a, b) => a + b, 0);
+    if (total === 0) {
+        return {vowel_fraction: 0, consonant_fraction: 0, deviation_from_english: 0};
+    }
+    const vowel_fraction = VOWELS.reduce((a, i) => a + counts[i], 0)/total;
+    let squared_distance = 0;
+    counts.forEach((count, i) => squared_distance += (count/total - ENGLISH_LETTER_FREQUENCIES[i])**2);
+    return {vowel_fraction: vowel_fraction, consonant_fraction: 1 - vowel_fraction, deviation_from_english: Math.sqrt(squared_distance)};
+}
+
+/**
+ * Compares the mix of letters on a board to English, e.g. to spot boards that look unusual
+ * @param board The board array (e.g. `GameState.board`)
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @returns The fractions of vowels and consonants, and how far the letter frequencies are from English
+ */
+export function compute_letter_balance(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number) {
+    return letter_balance(board_from_array(board), min_col, max_col, min_row, max_row);
+}
+
+/**
+ * Checks whether a `word` can be made using the given `letters`
+ * @param word The array form of the word to check
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @returns Whether `word` can be made using `letters
+ *
+ */
+function is_makeable(word: Uint8Array, letters: Uint8Array) {
+    const available_letters = new Uint8Array(letters.length);
+    for (let i=0; i<letters.length; i++) {
+        available_letters[i] = letters[i];
+    }
+    for (let i=0; i<word.length; i++) {
+        if (available_letters[word[i]] === 0) {
+            return false;
+        }
+        available_letters[word[i]] -= 1;
+    }
+    return true;
+}
+
+/**
+ * Checks whether a `word` can be made using the given `letters`, and if so how much of the hand it uses
+ * @param word The array form of the word to check
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @param most_stocked The number of tiles of whichever letter(s) the hand has the most of
+ * @returns `null` if `word` can't be made from `letters`; otherwise the number of tiles it uses from the hand, and how many of those are of the hand's most-stocked letter(s)
+ */
+function hand_consumption(word: Uint8Array, letters: Uint8Array, most_stocked: number): [number, number]|null {
+    if (!is_makeable(word, letters)) {
+        return null;
+    }
+    let surplus_used = 0;
+    for (const letter of word) {
+        if (letters[letter] === most_stocked) {
+            surplus_used += 1;
+        }
+    }
+    return [word.length, surplus_used];
+}
+
+/**
+ * Checks which words can be played after the first
+ * @param letters Length-26 array of originally available letters
+ * @param word_being_checked Word that is being checked if playable
+ * @param played_on_board Set of the letters played on the board
+ * @param filter_letters_on_board How many letters of the word may be played through the board rather than taken from `letters`
+ * @returns Whether the `word_being_checked` is playable
+ */
+function check_filter_after_play(letters: Uint8Array, word_being_checked: Uint8Array, played_on_board: Set<number>, filter_letters_on_board: number) {
+    const available_letters = new Int8Array(letters);
+    let borrowed = 0;
+    for (const letter of word_being_checked) {
+        if (available_letters[letter] <= 0) {
+            if (!played_on_board.has(letter) || borrowed >= filter_letters_on_board) {
+                return false;
+            }
+            borrowed += 1;
+        }
+        available_letters[letter] -= 1;
+    }
+    return true;
+}
+
+/**
+ * Gets the letters of the full run passing through `(row, col)` in the given direction
+ * @param board `Board` being checked
+ * @param row Row of a cell in the run
+ * @param col Column of a cell in the run
+ * @param direction Direction of the run
+ * @returns The letters of the run from start to end; empty if `(row, col)` is empty
+ */
+function get_run_through(board: Board, row: number, col: number, direction: "horizontal"|"vertical") {
+    const run: number[] = [];
+    if (direction === "horizontal") {
+        let start = col;
+        while (start > 0 && board.get_val(row, start-1) != EMPTY_VALUE) {
+            start -= 1;
+        }
+        for (let c=start; c<BOARD_SIZE && board.get_val(row, c) != EMPTY_VALUE; c++) {
+            run.push(board.get_val(row, c));
+        }
+    }
+    else {
+        let start = row;
+        while (start > 0 && board.get_val(start-1, col) != EMPTY_VALUE) {
+            start -= 1;
+        }
+        for (let r=start; r<BOARD_SIZE && board.get_val(r, col) != EMPTY_VALUE; r++) {
+            run.push(board.get_val(r, col));
+        }
+    }
+    return run;
+}
+
+/**
+ * Checks that a `board` is valid after a word is played, looking only at the words that the play could have changed:
+ * the full word along the direction of play, and the crossing word through each newly played letter.
+ * Note that this does not check if all words are contiguous; this condition must be enforced elsewhere.
+ * @param board `Board` being checked
+ * @param row Starting row of the word played
+ * @param col Starting column of the word played
+ * @param direction Direction the word was played in
+ * @param played_indices The indices at which new letters were played (see `play_word`)
+ * @param valid_words Set of all valid words
+ * @param no_two_letter_words Whether two-letter words are invalid even if they're in `valid_words`
+ * @returns Whether every word touched by the play is valid
+ */
+function check_word_and_crossings(board: Board, row: number, col: number, direction: "horizontal"|"vertical", played_indices: Array<[number, number]>, valid_words: Set<number>, no_two_letter_words = false) {
+    if (!is_valid_run(get_run_through(board, row, col, direction), valid_words, no_two_letter_words)) {
+        return false;
+    }
+    const crossing_direction = direction === "horizontal" ? "vertical" : "horizontal";
+    return played_indices.every(([r, c]) => is_valid_run(get_run_through(board, r, c, crossing_direction), valid_words, no_two_letter_words));
+}
+
+/**
+ * Checks whether a run of letters is allowed on the board
+ * @param run The letters of the run (see `get_run_through`)
+ * @param valid_words Set of all valid words
+ * @param no_two_letter_words Whether two-letter runs are invalid even if they're in `valid_words`
+ * @returns Whether the run is a single letter (or empty), or a valid word
+ */
+function is_valid_run(run: number[], valid_words: Set<number>, no_two_letter_words = false) {
+    return run.length < 2 || (!(no_two_letter_words && run.length === 2) && valid_words.has(vec_hasher(run)));
+}
+
+/**
+ * Checks every horizontal word in part of a row, e.g. after extending a word at its end without crossing anything
+ * @param board The board array (e.g. `GameState.board`)
+ * @param row Row to check
+ * @param min_col Minimum column index to check; words starting before it are checked in full
+ * @param max_col Maximum column index to check; words ending after it are checked in full
+ * @param valid_words_set Set of all valid hashed words (see `hash_dictionary`)
+ * @returns Whether every horizontal word touching the columns is valid
+ */
+export function validate_row_words(board: Uint8Array, row: number, min_col: number, max_col: number, valid_words_set: Set<number>) {
+    const wrapped = board_from_array(board);
+    for (let col=min_col; col<max_col+1; col++) {
+        // Only check each word once, from its first letter in the range
+        if (wrapped.get_val(row, col) != EMPTY_VALUE && (col == min_col || wrapped.get_val(row, col-1) == EMPTY_VALUE) && !is_valid_run(get_run_through(wrapped, row, col, "horizontal"), valid_words_set)) {
+            return false;
+        }
+    }
+    return true;
+}
+
+/**
+ * Checks the vertical words crossing part of a row, e.g. after playing a word horizontally across those columns
+ * @param board The board array (e.g. `GameState.board`)
+ * @param row Row being crossed
+ * @param start_col First column to check
+ * @param end_col Last column to check
+ * @param valid_words_set Set of all valid hashed words (see `hash_dictionary`)
+ * @returns Whether every vertical word through the row between the columns is valid
+ */
+export function validate_col_crossings(board: Uint8Array, row: number, start_col: number, end_col: number, valid_words_set: Set<number>) {
+    const wrapped = board_from_array(board);
+    for (let col=start_col; col<end_col+1; col++) {
+        if (!is_valid_run(get_run_through(wrapped, row, col, "vertical"), valid_words_set)) {
+            return false;
+        }
+    }
+    return true;
+}
+
+/**
+ * A word on the board that isn't in the dictionary
+ */
+export interface InvalidWord extends PlacedWord {
+    /**
+     * Row of the word's last letter
+     */
+    end_row: number,
+    /**
+     * Column of the word's last letter
+     */
+    end_col: number
+}
+
+/**
+ * Async function to find every word on a board that isn't in the dictionary, e.g. so that all of them can be highlighted at once on a board the user built
+ * @param board The board array (e.g. `GameState.board`)
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether to check against the full Scrabble dictionary rather than just the common words
+ * @returns Every invalid word, horizontal words from top to bottom followed by vertical words from left to right; empty if the board is valid
+ */
+export async function validate_board(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number, state: AppState, use_long_dictionary: boolean) {
+    return new Promise<InvalidWord[]>((resolve, reject) => {
+        const unavailable = dictionary_unavailable(use_long_dictionary);
+        if (unavailable != null) {
+            reject(unavailable);
+            return;
+        }
+        if (min_col < 0 || min_row < 0 || max_col >= BOARD_SIZE || max_row >= BOARD_SIZE) {
+            reject("The board's bounds are outside of the board");
+            return;
+        }
+        const valid_words_set = use_long_dictionary ? dictionary_set(state.all_words_long) : state.all_words_short_set;
+        resolve(invalid_words(board_from_array(board), min_col, max_col, min_row, max_row, valid_words_set));
+    });
+}
+
+/**
+ * Finds every word on the board that isn't in the dictionary
+ * @param board `Board` to check
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @param valid_words_set Set of all valid hashed words
+ * @returns Every invalid word, horizontal words from top to bottom followed by vertical words from left to right
+ */
+function invalid_words(board: Board, min_col: number, max_col: number, min_row: number, max_row: number, valid_words_set: Set<number>) {
+    const invalid: InvalidWord[] = [];
+    for (const placed of extract_placed_words(board, min_col, max_col, min_row, max_row)) {
+        if (!valid_words_set.has(vec_hasher(convert_word_to_array(placed.word)))) {
+            const end_offset = placed.word.length - 1;
+            invalid.push({...placed, end_row: placed.direction === "vertical" ? placed.row + end_offset : placed.row, end_col: placed.direction === "horizontal" ? placed.col + end_offset : placed.col});
+        }
+    }
+    return invalid;
+}
+
+/**
+ * Plays a word on the board
+ * @param word The word to be played
+ * @param row_idx The starting row at which to play the word
+ * @param col_idx The starting column at which to play the word
+ * @param board The current board (is modified in-place)
+ * @param direction The direction in which to play the word
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @param remaining_letters_out Length-26 array to reuse for the remaining letters rather than allocating a new one (see `SearchScratch`)
+ * @param played_indices_out Array to reuse for the played indices rather than allocating a new one; it's emptied first
+ * @returns Whether the word could be validly played, which indices it was played on, the remaining letters, and the letter usage; or `null` if any of the word would be out-of-bounds
+ */
+export function play_word(word: Uint8Array, row_idx: number, col_idx: number, board: Board, direction: "horizontal"|"vertical", letters: Uint8Array, remaining_letters_out?: Uint8Array, played_indices_out?: Array<[number, number]>): [boolean, Array<[number, number]>, Uint8Array, "Remaining"|"Overused"|"Finished"]|null {
+    const played_indices = played_indices_out ?? [];
+    played_indices.length = 0;
+    let remaining_letters: Uint8Array;
+    if (remaining_letters_out != null) {
+        remaining_letters_out.set(letters);
+        remaining_letters = remaining_letters_out;
+    }
+    else {
+        remaining_letters = Uint8Array.from(letters);
+    }
+    if (row_idx < 0 || col_idx < 0 || row_idx >= BOARD_SIZE || col_idx >= BOARD_SIZE) {
+        return null;
+    }
+    if (direction === "horizontal") {
+        // A word may end in the last column
+        if (col_idx + word.length > BOARD_SIZE) {
+            return null;
+        }
+        // Check if the word will start or end at a letter
+        let valid_loc = (col_idx != 0 && board.get_val(row_idx, col_idx-1) != EMPTY_VALUE) || (col_idx+word.length < BOARD_SIZE && board.get_val(row_idx, col_idx+word.length) != EMPTY_VALUE);
+        // Check if the word will border any letters on the top or bottom
+        if (!valid_loc) {
+            for (let c_idx=col_idx; c_idx<col_idx+word.length; c_idx++) {
+                if ((row_idx < BOARD_SIZE-1 && board.get_val(row_idx+1, c_idx) != EMPTY_VALUE) || (row_idx > 0 && board.get_val(row_idx-1, c_idx) != EMPTY_VALUE)) {
+                    valid_loc = true;
+                    break;
+                }
+            }
+        }
+        if (!valid_loc) {
+            return [false, played_indices, remaining_letters, "Remaining"];
+        }
+        else {
+            let entirely_overlaps = true;
+            for (let i=0; i<word.length; i++) {
+                if (board.get_val(row_idx, col_idx+i) == EMPTY_VALUE) {
+                    board.set_val(row_idx, col_idx+i, word[i]);
+                    played_indices.push([row_idx, col_idx+i]);
+                    entirely_overlaps = false;
+                    if (remaining_letters[word[i]] === 0) {
+                        return [false, played_indices, remaining_letters, "Overused"];
+                    }
+                    remaining_letters[word[i]] -= 1;
+                }
+                else if (board.get_val(row_idx, col_idx+i) !== word[i]) {
+                    return [false, played_indices, remaining_letters, "Remaining"];
+                }
+            }
+            if (remaining_letters.every(count => count === 0) && !entirely_overlaps) {
+                return [true, played_indices, remaining_letters, "Finished"];
+            }
+            else {
+                return [!entirely_overlaps, played_indices, remaining_letters, "Remaining"];
+            }
+        }
+    }
+    else {
+        // A word may end in the last row
+        if (row_idx + word.length > BOARD_SIZE) {
+            return null;
+        }
+        // Check if the word will start or end at a letter
+        let valid_loc = (row_idx != 0 && board.get_val(row_idx-1, col_idx) != EMPTY_VALUE) || (row_idx+word.length < BOARD_SIZE && board.get_val(row_idx+word.length, col_idx) != EMPTY_VALUE);
+        // Check if the word will border any letters on the right or left
+        if (!valid_loc) {
+            for (let r_idx=row_idx; r_idx<row_idx+word.length; r_idx++) {
+                if ((col_idx < BOARD_SIZE-1 && board.get_val(r_idx, col_idx+1) != EMPTY_VALUE) || (col_idx > 0 && board.get_val(r_idx, col_idx-1) != EMPTY_VALUE)) {
+                    valid_loc = true;
+                    break;
+                }
+            }
+        }
+        if (!valid_loc) {
+            return [false, played_indices, remaining_letters, "Remaining"];
+        }
+        else {
+            let entirely_overlaps = true;
+            for (let i=0; i<word.length; i++) {
+                if (board.get_val(row_idx+i, col_idx) == EMPTY_VALUE) {
+                    board.set_val(row_idx+i, col_idx, word[i]);
+                    played_indices.push([row_idx+i, col_idx]);
+                    entirely_overlaps = false;
+                    if (remaining_letters[word[i]] === 0) {
+                        return [false, played_indices, remaining_letters, "Overused"];
+                    }
+                    remaining_letters[word[i]] -= 1;
+                }
+                else if (board.get_val(row_idx+i, col_idx) !== word[i]) {
+                    return [false, played_indices, remaining_letters, "Remaining"];
+                }
+            }
+            if (remaining_letters.every(count => count == 0) && !entirely_overlaps) {
+                return [true, played_indices, remaining_letters, "Finished"];
+            }
+            else {
+                return [!entirely_overlaps, played_indices, remaining_letters, "Remaining"];
+            }
+        }
+    }
+}
+
+/**
+ * Undoes a play on the `board`
+ * @param board `Board` being undone (is modified in-place)
+ * @param played_indices Array of the indices in `board` that need to be reset
+ */
+function undo_play(board: Board, played_indices: Array<[number, number]>) {
+    for (const index of played_indices) {
+        board.set_val(index[0], index[1], EMPTY_VALUE);
+    }
+}
+
+/**
+ * Checks whether a word could be validly played at a location without modifying the `board`
+ * @param board The current `Board` (not modified)
+ * @param word The word to check
+ * @param row_idx The starting row at which to play the word
+ * @param col_idx The starting column at which to play the word
+ * @param direction The direction in which to play the word
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @param valid_words_set Set of all valid hashed words
+ * @param scratch `Board` to play on instead of `board`; pass the same one when checking many locations to avoid allocating a new board each time
+ * @returns If the word can be played there using the hand, bordering the existing letters and forming only valid words, the indices at which new letters would be played and the letters that would remain in the hand; otherwise `null`
+ */
+function can_place_word_safely(board: Board, word: Uint8Array, row_idx: number, col_idx: number, direction: "horizontal"|"vertical", letters: Uint8Array, valid_words_set: Set<number>, scratch: Board = new Board()): [Array<[number, number]>, Uint8Array]|null {
+    if (row_idx < 0 || col_idx < 0) {
+        return null;
+    }
+    scratch.arr.set(board.arr);
+    const res = play_word(word, row_idx, col_idx, scratch, direction, letters);
+    if (res != null && res[0] && check_word_and_crossings(scratch, row_idx, col_idx, direction, res[1], valid_words_set)) {
+        return [res[1], res[2]];
+    }
+    return null;
+}
+
+/**
+ * Generates every starting location at which a word could touch the existing tiles
+ * @param word_length Length of the word
+ * @param direction The direction the word would be played in
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @returns Iterator over the `[row, col]` starting locations; horizontal words go across each row in turn (starting from the farthest out the word could be played), and vertical words down each column
+ */
+function* candidate_starts(word_length: number, direction: "horizontal"|"vertical", min_col: number, max_col: number, min_row: number, max_row: number): Generator<[number, number]> {
+    if (direction === "horizontal") {
+        // Try across all rows (starting from one before to one after)
+        for (let row_idx=min_row-1; row_idx<max_row+2; row_idx++) {
+            for (let col_idx=min_col-word_length; col_idx<max_col+2; col_idx++) {
+                yield [row_idx, col_idx];
+            }
+        }
+    }
+    else {
+        // Try down all columns; this is analgous to the above
+        for (let col_idx=min_col-1; col_idx<max_col+2; col_idx++) {
+            for (let row_idx=min_row-word_length; row_idx<max_row+2; row_idx++) {
+                yield [row_idx, col_idx];
+            }
+        }
+    }
+}
+
+/**
+ * Checks whether a word can't be played at a location because the tiles already on the board reach the edge of the board array on the side it runs off of
+ * @param word_length Length of the word
+ * @param row_idx The starting row of the word
+ * @param col_idx The starting column of the word
+ * @param direction The direction the word would be played in
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @returns Whether the word runs off the board past an occupied edge row or column; `false` if it fits, or only runs off where the board could still grow
+ */
+function blocked_by_grid_edge(word_length: number, row_idx: number, col_idx: number, direction: "horizontal"|"vertical", min_col: number, max_col: number, min_row: number, max_row: number) {
+    const end_row = direction === "horizontal" ? row_idx : row_idx+word_length-1;
+    const end_col = direction === "horizontal" ? col_idx+word_length-1 : col_idx;
+    return (row_idx < 0 && min_row === 0) || (col_idx < 0 && min_col === 0) || (end_row >= BOARD_SIZE && max_row === BOARD_SIZE-1) || (end_col >= BOARD_SIZE && max_col === BOARD_SIZE-1);
+}
+
+/**
+ * Counts how many letters of a word would land on tiles already on the board
+ * @param board The current `Board`
+ * @param word The word
+ * @param row_idx The starting row of the word
+ * @param col_idx The starting column of the word
+ * @param direction The direction the word would be played in
+ * @returns The number of occupied cells the word would cover
+ */
+function count_overlap(board: Board, word: Uint8Array, row_idx: number, col_idx: number, direction: "horizontal"|"vertical") {
+    let overlap = 0;
+    for (let i=0; i<word.length; i++) {
+        if ((direction === "horizontal" ? board.get_val(row_idx, col_idx+i) : board.get_val(row_idx+i, col_idx)) != EMPTY_VALUE) {
+            overlap += 1;
+        }
+    }
+    return overlap;
+}
+
+/**
+ * Gets how many letters a word must share with the tiles already on the board under `SolverConfig.min_intersections_per_word`; a word can
+ * share at most one letter with each word played across it, so beyond one (which crossing any word meets) this is capped at how many of those there are
+ * @param config Options for the search
+ * @param direction The direction the word would be played in
+ * @param play_sequence The words played so far
+ * @returns The fewest letters the word must share
+ */
+function required_intersections(config: SolverConfig, direction: "horizontal"|"vertical", play_sequence: PlaySequence) {
+    const min_intersections = config.min_intersections_per_word ?? 0;
+    if (min_intersections <= 1) {
+        return min_intersections;
+    }
+    const across = play_sequence.filter(([, [, , played_direction]]) => played_direction !== direction).length;
+    return Math.min(min_intersections, Math.max(1, across));
+}
+
+/**
+ * Generates the starting locations for a word in the order the solver should try them: the same as `candidate_starts`, except that
+ * with a soft `SolverConfig.min_intersections_per_word` the locations meeting it are all tried first
+ * @param board The current `Board`
+ * @param word The word
+ * @param direction The direction the word would be played in
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @param play_sequence The words played so far
+ * @param config Options for the search
+ * @returns Iterator over the `[row, col]` starting locations
+ */
+function* ordered_starts(board: Board, word: Uint8Array, direction: "horizontal"|"vertical", min_col: number, max_col: number, min_row: number, max_row: number, play_sequence: PlaySequence, config: SolverConfig): Generator<[number, number]> {
+    const min_intersections = required_intersections(config, direction, play_sequence);
+    if (min_intersections === 0 || config.intersection_mode === "hard") {
+        yield* candidate_starts(word.length, direction, min_col, max_col, min_row, max_row);
+        return;
+    }
+    const deferred: Array<[number, number]> = [];
+    for (const [row_idx, col_idx] of candidate_starts(word.length, direction, min_col, max_col, min_row, max_row)) {
+        if (row_idx >= 0 && col_idx >= 0 && count_overlap(board, word, row_idx, col_idx, direction) < min_intersections) {
+            deferred.push([row_idx, col_idx]);
+        }
+        else {
+            yield [row_idx, col_idx];
+        }
+    }
+    yield* deferred;
+}
+
+/**
+ * Gets the valid starting locations for a word in the order to try them when `SolverConfig.lookahead_tiebreak` is set: by how much they grow the bounding box,
+ * then by how many words could still be played afterwards (see `check_filter_after_play`), and otherwise in the same order as `ordered_starts`
+ * @param board The current `Board` (modified and restored)
+ * @param word The word
+ * @param direction The direction the word would be played in
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @param valid_words_vec Array of arrays, each representing a word (see `convert_word_to_array`)
+ * @param valid_words_set Set of hashed word arrays
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @param depth Depth of the current recursive call
+ * @param play_sequence The words played so far
+ * @param ctx Bookkeeping for the whole search
+ * @returns The `[row, col]` starting locations at which the word forms only valid words
+ */
+function lookahead_starts(board: Board, word: Uint8Array, direction: "horizontal"|"vertical", min_col: number, max_col: number, min_row: number, max_row: number, valid_words_vec: Array<Uint8Array>, valid_words_set: Set<number>, letters: Uint8Array, depth: number, play_sequence: PlaySequence, ctx: SearchContext) {
+    const area = (max_col - min_col + 1)*(max_row - min_row + 1);
+    const placements: Array<{start: [number, number], growth: number, remaining: Uint8Array, follow_on: number}> = [];
+    for (const [row_idx, col_idx] of ordered_starts(board, word, direction, min_col, max_col, min_row, max_row, play_sequence, ctx.config)) {
+        const res = play_word(word, row_idx, col_idx, board, direction, letters, ...scratch_at(ctx.scratch, depth));
+        if (res == null) {
+            // Skipped just like in `try_play_word`
+            if (blocked_by_grid_edge(word.length, row_idx, col_idx, direction, min_col, max_col, min_row, max_row)) {
+                ctx.reached_edge = true;
+            }
+            continue;
+        }
+        if (res[0] && check_word_and_crossings(board, row_idx, col_idx, direction, res[1], valid_words_set, ctx.config.no_two_letter_words)) {
+            const width = Math.max(max_col, direction === "horizontal" ? col_idx+word.length-1 : col_idx) - Math.min(min_col, col_idx) + 1;
+            const height = Math.max(max_row, direction === "horizontal" ? row_idx : row_idx+word.length-1) - Math.min(min_row, row_idx) + 1;
+            placements.push({start: [row_idx, col_idx], growth: width*height - area, remaining: Uint8Array.from(res[2]), follow_on: 0});
+        }
+        undo_play(board, res[1]);
+    }
+    // Only look ahead for placements that tie on growth
+    const ties = new Map<number, number>();
+    placements.forEach(placement => ties.set(placement.growth, (ties.get(placement.growth) ?? 0) + 1));
+    const word_letters = new Set(word);
+    for (const placement of placements) {
+        if (ties.get(placement.growth)! > 1) {
+            placement.follow_on = valid_words_vec.reduce((a, w) => a + Number(check_filter_after_play(placement.remaining, w, word_letters, ctx.config.filter_letters_on_board ?? DEFAULT_FILTER_LETTERS_ON_BOARD)), 0);
+        }
+    }
+    // Stable, so otherwise tied placements stay in order
+    placements.sort((a, b) => a.growth - b.growth || b.follow_on - a.follow_on);
+    return placements.map(placement => placement.start);
+}
+
+/**
+ * Cache of the hashed form of each dictionary (see `hash_dictionary`)
+ */
+const DICTIONARY_SETS = new WeakMap<Array<Uint8Array>, Set<number>>();
+
+/**
+ * Gets the hashed form of a dictionary, hashing it the first time it's seen
+ * @param dictionary Array of words (see `convert_word_to_array`)
+ * @returns Set of the hash of each word in `dictionary`
+ */
+function dictionary_set(dictionary: Array<Uint8Array>) {
+    let set = DICTIONARY_SETS.get(dictionary);
+    if (set == null) {
+        set = hash_dictionary(dictionary);
+        DICTIONARY_SETS.set(dictionary, set);
+    }
+    return set;
+}
+
+/**
+ * A valid placement of a word on an existing board
+ */
+export interface WordPlacement {
+    /**
+     * The word placed
+     */
+    word: string,
+    /**
+     * Starting row of the word
+     */
+    row: number,
+    /**
+     * Starting column of the word
+     */
+    col: number,
+    /**
+     * Direction the word is played in
+     */
+    direction: "horizontal"|"vertical",
+    /**
+     * The letters that would be used from the hand
+     */
+    letters_used: string,
+    /**
+     * How many of the word's letters are already on the board
+     */
+    overlap: number
+}
+
+/**
+ * Builds a `WordPlacement` from a successful dry run
+ * @param word The word placed
+ * @param row Starting row of the word
+ * @param col Starting column of the word
+ * @param direction Direction the word is played in
+ * @param letters Length-26 array of the number of each letter in the hand before the placement
+ * @param remaining Length-26 array of the number of each letter in the hand after the placement
+ * @returns The placement
+ */
+function to_word_placement(word: Uint8Array, row: number, col: number, direction: "horizontal"|"vertical", letters: Uint8Array, remaining: Uint8Array): WordPlacement {
+    let letters_used = "";
+    let tiles_used = 0;
+    for (let i=0; i<26; i++) {
+        letters_used += UPPERCASE.charAt(i).repeat(letters[i] - remaining[i]);
+        tiles_used += letters[i] - remaining[i];
+    }
+    return {word: convert_array_to_word(word), row: row, col: col, direction: direction, letters_used: letters_used, overlap: word.length - tiles_used};
+}
+
+/**
+ * The placements found through a cell
+ */
+export interface CellPlacements {
+    /**
+     * Every valid placement found covering the cell
+     */
+    placements: WordPlacement[],
+    /**
+     * Whether the search stopped early because `limit` or `max_words_to_check` was reached
+     */
+    truncated: boolean
+}
+
+/**
+ * Async function to find the words that could be played through a specific cell of an existing board
+ * @param board The board array (e.g. `GameState.board`); not modified
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @param available_letters Mapping of string letters to numeric quantity of each letter in the hand
+ * @param row Row of the cell
+ * @param col Column of the cell
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether to use the full Scrabble dictionary rather than the common words
+ * @param limit Maximum number of placements to return
+ * @param max_words_to_check Maximum number of placements to try before giving up
+ * @returns The valid placements of words covering the cell, in dictionary order
+ */
+export async function words_through_cell(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number, available_letters: Map<string, number>, row: number, col: number, state: AppState, use_long_dictionary: boolean, limit: number, max_words_to_check = Infinity) {
+    return new Promise<CellPlacements>((resolve, reject) => {
+        const unavailable = dictionary_unavailable(use_long_dictionary);
+        if (unavailable != null) {
+            reject(unavailable);
+            return;
+        }
+        const letters = letters_from_map(available_letters);
+        if (typeof letters === "string") {
+            reject(letters);
+            return;
+        }
+        if (row < min_row-1 || row > max_row+1 || col < min_col-1 || col > max_col+1) {
+            reject("The cell (" + row + ", " + col + ") is not on or next to the board");
+            return;
+        }
+        const wrapped = board_from_array(board);
+        const scratch = new Board();
+        const dictionary = get_dictionary(state, use_long_dictionary);
+        const valid_words_set = dictionary_set(dictionary);
+        // Words can only use the hand plus letters already in the cell's row or column
+        const row_letters = Uint8Array.from(letters);
+        const col_letters = Uint8Array.from(letters);
+        for (let c=min_col; c<max_col+1; c++) {
+            if (wrapped.get_val(row, c) != EMPTY_VALUE) {
+                row_letters[wrapped.get_val(row, c)] += 1;
+            }
+        }
+        for (let r=min_row; r<max_row+1; r++) {
+            if (wrapped.get_val(r, col) != EMPTY_VALUE) {
+                col_letters[wrapped.get_val(r, col)] += 1;
+            }
+        }
+        const placements: WordPlacement[] = [];
+        let checked = 0;
+        for (const word of dictionary) {
+            for (const direction of ["horizontal", "vertical"] as const) {
+                if (!is_makeable(word, direction === "horizontal" ? row_letters : col_letters)) {
+                    continue;
+                }
+                for (let i=0; i<word.length; i++) {
+                    if (placements.length >= limit || checked >= max_words_to_check) {
+                        resolve({placements: placements, truncated: true});
+                        return;
+                    }
+                    checked += 1;
+                    const start_row = direction === "horizontal" ? row : row - i;
+                    const start_col = direction === "horizontal" ? col - i : col;
+                    const res = can_place_word_safely(wrapped, word, start_row, start_col, direction, letters, valid_words_set, scratch);
+                    if (res != null) {
+                        placements.push(to_word_placement(word, start_row, start_col, direction, letters, res[1]));
+                    }
+                }
+            }
+        }
+        resolve({placements: placements, truncated: false});
+    });
+}
+
+/**
+ * How many placements would put a tile in an empty cell next to the board
+ */
+export interface ExtensionHotspot {
+    /**
+     * Row of the cell
+     */
+    row: number,
+    /**
+     * Column of the cell
+     */
+    col: number,
+    /**
+     * Number of valid placements of makeable words that would play a tile in the cell
+     */
+    placements: number
+}
+
+/**
+ * Where words could be played on a board
+ */
+export interface ExtensionHotspots {
+    /**
+     * Every empty cell next to the board, from top to bottom and left to right
+     */
+    cells: ExtensionHotspot[],
+    /**
+     * Whether the search stopped early because `max_words_to_check` was reached, in which case the counts are too low
+     */
+    truncated: boolean
+}
+
+/**
+ * Async function to count, for each empty cell next to a board, how many placements of makeable words would play a tile there, e.g. to show a heat map of where to play next
+ * @param board The board array (e.g. `GameState.board`); not modified
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @param available_letters Mapping of string letters to numeric quantity of each letter in the hand
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether to use the full Scrabble dictionary rather than the common words
+ * @param max_words_to_check Maximum number of placements to try before giving up
+ * @returns The number of placements through each empty cell next to the board
+ */
+export async function extension_hotspots(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number, available_letters: Map<string, number>, state: AppState, use_long_dictionary: boolean, max_words_to_check = Infinity) {
+    return new Promise<ExtensionHotspots>((resolve, reject) => {
+        const unavailable = dictionary_unavailable(use_long_dictionary);
+        if (unavailable != null) {
+            reject(unavailable);
+            return;
+        }
+        const letters = letters_from_map(available_letters);
+        if (typeof letters === "string") {
+            reject(letters);
+            return;
+        }
+        const wrapped = board_from_array(board);
+        const scratch = new Board();
+        const dictionary = get_dictionary(state, use_long_dictionary);
+        const valid_words_set = dictionary_set(dictionary);
+        // Count by index into the board, only for the empty cells next to an occupied one
+        const counts = new Map<number, number>();
+        for (let row=Math.max(0, min_row-1); row<Math.min(BOARD_SIZE, max_row+2); row++) {
+            for (let col=Math.max(0, min_col-1); col<Math.min(BOARD_SIZE, max_col+2); col++) {
+                const next_to_board = (row > 0 && wrapped.get_val(row-1, col) != EMPTY_VALUE) || (row < BOARD_SIZE-1 && wrapped.get_val(row+1, col) != EMPTY_VALUE)
+                    || (col > 0 && wrapped.get_val(row, col-1) != EMPTY_VALUE) || (col < BOARD_SIZE-1 && wrapped.get_val(row, col+1) != EMPTY_VALUE);
+                if (wrapped.get_val(row, col) == EMPTY_VALUE && next_to_board) {
+                    counts.set(row*BOARD_SIZE + col, 0);
+                }
+            }
+        }
+        // Words can only use the hand plus letters already on the board
+        const all_letters = count_board_letters(board, min_col, max_col, min_row, max_row);
+        letters.forEach((count, i) => all_letters[i] += count);
+        let checked = 0;
+        let truncated = false;
+        search: for (const word of dictionary) {
+            if (!is_makeable(word, all_letters)) {
+                continue;
+            }
+            for (const direction of ["horizontal", "vertical"] as const) {
+                for (const [row, col] of candidate_starts(word.length, direction, min_col, max_col, min_row, max_row)) {
+                    if (checked >= max_words_to_check) {
+                        truncated = true;
+                        break search;
+                    }
+                    checked += 1;
+                    const res = can_place_word_safely(wrapped, word, row, col, direction, letters, valid_words_set, scratch);
+                    if (res != null) {
+                        for (const [played_row, played_col] of res[0]) {
+                            const idx = played_row*BOARD_SIZE + played_col;
+                            if (counts.has(idx)) {
+                                counts.set(idx, counts.get(idx)! + 1);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        const cells = Array.from(counts, ([idx, placements]) => ({row: Math.floor(idx/BOARD_SIZE), col: idx % BOARD_SIZE, placements: placements}));
+        resolve({cells: cells, truncated: truncated});
+    });
+}
+
+/**
+ * A valid placement of a word, along with the bounds of the board after playing it
+ */
+export interface BoundedWordPlacement extends WordPlacement {
+    /**
+     * Minimum occupied column index after the placement
+     */
+    min_col: number,
+    /**
+     * Maximum occupied column index after the placement
+     */
+    max_col: number,
+    /**
+     * Minimum occupied row index after the placement
+     */
+    min_row: number,
+    /**
+     * Maximum occupied row index after the placement
+     */
+    max_row: number
+}
+
+/**
+ * Async function to find everywhere a specific word could be played on an existing board
+ * @param board The board array (e.g. `GameState.board`); not modified
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @param word The word to play
+ * @param available_letters Mapping of string letters to numeric quantity of each letter in the hand
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether to use the full Scrabble dictionary rather than the common words
+ * @returns Every valid placement of the word, with the most letters already on the board first
+ */
+export async function placements_for_word(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number, word: string, available_letters: Map<string, number>, state: AppState, use_long_dictionary: boolean) {
+    return new Promise<BoundedWordPlacement[]>((resolve, reject) => {
+        const unavailable = dictionary_unavailable(use_long_dictionary);
+        if (unavailable != null) {
+            reject(unavailable);
+            return;
+        }
+        const letters = letters_from_map(available_letters);
+        if (typeof letters === "string") {
+            reject(letters);
+            return;
+        }
+        const upper = word.toUpperCase();
+        if (!/^[A-Z]+$/.test(upper)) {
+            reject("Words may only contain the letters A-Z");
+            return;
+        }
+        const word_arr = convert_word_to_array(upper);
+        const valid_words_set = dictionary_set(get_dictionary(state, use_long_dictionary));
+        if (!valid_words_set.has(vec_hasher(word_arr))) {
+            reject(upper + " is not in the dictionary");
+            return;
+        }
+        const wrapped = board_from_array(board);
+        const scratch = new Board();
+        const placements: BoundedWordPlacement[] = [];
+        for (const direction of ["horizontal", "vertical"] as const) {
+            for (const [row, col] of candidate_starts(word_arr.length, direction, min_col, max_col, min_row, max_row)) {
+                const res = can_place_word_safely(wrapped, word_arr, row, col, direction, letters, valid_words_set, scratch);
+                if (res != null) {
+                    placements.push({
+                        ...to_word_placement(word_arr, row, col, direction, letters, res[1]),
+                        min_col: Math.min(min_col, col),
+                        max_col: Math.max(max_col, direction === "horizontal" ? col+word_arr.length-1 : col),
+                        min_row: Math.min(min_row, row),
+                        max_row: Math.max(max_row, direction === "horizontal" ? row : row+word_arr.length-1)
+                    });
+                }
+            }
+        }
+        // Stable, so ties stay in scanning order
+        placements.sort((a, b) => b.overlap - a.overlap);
+        resolve(placements);
+    });
+}
+
+/**
+ * Lays out a list of words on a board so that they cross at shared letters (the inverse of solving)
+ * 
+ * Greedy: the longest word is played first, then each remaining word is attached wherever it first fits, longest first, until every word is placed or none of the rest fit.
+ * Any other runs of letters the words form must also be words in the list.
+ * @param words The words to lay out
+ * @param allow_extra_intersections Whether a word may share more than one letter with the words already played
+ * @returns The board with every word played, or `null` if the words couldn't all be placed
+ */
+export function build_board_from_words(words: string[], allow_extra_intersections: boolean): GameState|null {
+    const to_place = words.map(word => convert_word_to_array(word.toUpperCase())).filter(word => word.length > 0);
+    if (to_place.length === 0) {
+        return null;
+    }
+    // Stable, so words of the same length are placed in the given order
+    to_place.sort((a, b) => b.length - a.length);
+    const valid_words_set = hash_dictionary(to_place);
+    const board = new Board();
+    const first = to_place.shift()!;
+    const row = board_center();
+    const col_start = Math.round(board_center() - first.length/2);
+    for (let i=0; i<first.length; i++) {
+        board.set_val(row, col_start+i, first[i]);
+    }
+    const play_sequence: PlaySequence = [[first, [row, col_start, "horizontal"]]];
+    let min_col = col_start;
+    let max_col = col_start + first.length - 1;
+    let min_row = row;
+    let max_row = row;
+    let placed_any = true;
+    while (to_place.length > 0 && placed_any) {
+        placed_any = false;
+        for (let i=0; i<to_place.length && !placed_any; i++) {
+            const word = to_place[i];
+            // Only the word's own letters can be played
+            const word_letters = new Uint8Array(26);
+            for (const letter of word) {
+                word_letters[letter] += 1;
+            }
+            for (const direction of ["horizontal", "vertical"] as const) {
+                for (const [row_idx, col_idx] of candidate_starts(word.length, direction, min_col, max_col, min_row, max_row)) {
+                    const res = row_idx < 0 || col_idx < 0 ? null : play_word(word, row_idx, col_idx, board, direction, word_letters);
+                    if (res == null) {
+                        continue;
+                    }
+                    const shared = word.length - res[1].length;
+                    if (res[0] && shared > 0 && (allow_extra_intersections || shared === 1) && check_word_and_crossings(board, row_idx, col_idx, direction, res[1], valid_words_set)) {
+                        play_sequence.push([word, [row_idx, col_idx, direction]]);
+                        min_col = Math.min(min_col, col_idx);
+                        max_col = Math.max(max_col, direction === "horizontal" ? col_idx+word.length-1 : col_idx);
+                        min_row = Math.min(min_row, row_idx);
+                        max_row = Math.max(max_row, direction === "horizontal" ? row_idx : row_idx+word.length-1);
+                        to_place.splice(i, 1);
+                        placed_any = true;
+                        break;
+                    }
+                    undo_play(board, res[1]);
+                }
+                if (placed_any) {
+                    break;
+                }
+            }
+        }
+    }
+    if (to_place.length > 0) {
+        return null;
+    }
+    // Shared letters are only on the board once, so the hand is what's on the board
+    const letters = new Uint8Array(26);
+    for (let r=min_row; r<max_row+1; r++) {
+        for (let c=min_col; c<max_col+1; c++) {
+            if (board.get_val(r, c) != EMPTY_VALUE) {
+                letters[board.get_val(r, c)] += 1;
+            }
+        }
+    }
+    return {board: board.arr, min_col: min_col, max_col: max_col, min_row: min_row, max_row: max_row, letters: letters, play_sequence: play_sequence};
+}
+
+/**
+ * Async function to read a board pasted as text, e.g. copied from a chat message or the output of a text recognition tool
+ * @param text The board, one row per line with a space for each empty cell (lines may be different lengths); tabs separate cells as copied from
+ * a spreadsheet, so a tab right after a letter only ends it, and any other tab is an empty cell
+ * @returns The board centered in a board array, along with its bounds and the letters on it
+ */
+export async function board_from_clipboard_text(text: string) {
+    return new Promise<GameState>((resolve, reject) => {
+        const lines = text.replace(/\r\n?/g, "\n").split("\n");
+        // Each piece between tabs is read as its own cells, and an empty piece is a single empty cell
+        const rows = lines.map(line => line.split("\t").flatMap(piece => piece === "" ? [""] : piece.split("")));
+        let [min_col, max_col, min_row, max_row] = [Infinity, -1, Infinity, -1];
+        for (let r=0; r<rows.length; r++) {
+            for (let c=0; c<rows[r].length; c++) {
+                const cell = rows[r][c].toUpperCase();
+                if (cell === "" || cell === " ") {
+                    continue;
+                }
+                if (cell.length > 1 || !UPPERCASE.includes(cell)) {
+                    reject("Line " + (r+1) + " contains \"" + rows[r][c] + "\", which isn't a letter");
+                    return;
+                }
+                min_col = Math.min(min_col, c);
+                max_col = Math.max(max_col, c);
+                min_row = Math.min(min_row, r);
+                max_row = Math.max(max_row, r);
+            }
+        }
+        if (max_row === -1) {
+            reject("No letters were found in the text");
+            return;
+        }
+        if (max_col - min_col + 1 > BOARD_SIZE || max_row - min_row + 1 > BOARD_SIZE) {
+            reject("The board can be at most " + BOARD_SIZE + " letters wide and tall");
+            return;
+        }
+        // Center the letters on the board, like the solver's opening word
+        const row_offset = Math.round(board_center() - (max_row - min_row + 1)/2) - min_row;
+        const col_offset = Math.round(board_center() - (max_col - min_col + 1)/2) - min_col;
+        const board = new Board();
+        for (let r=min_row; r<max_row+1; r++) {
+            for (let c=min_col; c<Math.min(max_col+1, rows[r].length); c++) {
+                const cell = rows[r][c].toUpperCase();
+                if (cell !== "" && cell !== " ") {
+                    board.set_val(r + row_offset, c + col_offset, cell.charCodeAt(0) - 65);
+                }
+            }
+        }
+        const [new_min_col, new_max_col, new_min_row, new_max_row] = [min_col + col_offset, max_col + col_offset, min_row + row_offset, max_row + row_offset];
+        resolve({board: board.arr, min_col: new_min_col, max_col: new_max_col, min_row: new_min_row, max_row: new_max_row, letters: count_board_letters(board.arr, new_min_col, new_max_col, new_min_row, new_max_row)});
+    });
+}
+
+/**
+ * Async function to check whether a word could be validly played at a location on an existing board (e.g. for hints)
+ * @param board The board array (e.g. `GameState.board`); not modified
+ * @param word The word to check
+ * @param row The starting row at which to play the word
+ * @param col The starting column at which to play the word
+ * @param direction The direction in which to play the word
+ * @param available_letters Mapping of string letters to numeric quantity of each letter in the hand
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether to use the full Scrabble dictionary rather than the common words
+ * @returns Whether the word can be played there
+ */
+export async function can_place_word(board: Uint8Array, word: string, row: number, col: number, direction: "horizontal"|"vertical", available_letters: Map<string, number>, state: AppState, use_long_dictionary: boolean) {
+    return new Promise<boolean>((resolve, reject) => {
+        const unavailable = dictionary_unavailable(use_long_dictionary);
+        if (unavailable != null) {
+            reject(unavailable);
+            return;
+        }
+        const letters = letters_from_map(available_letters);
+        if (typeof letters === "string") {
+            reject(letters);
+            return;
+        }
+        const upper = word.toUpperCase();
+        if (!/^[A-Z]+$/.test(upper)) {
+            reject("Words may only contain the letters A-Z");
+            return;
+        }
+        const valid_words_set = dictionary_set(get_dictionary(state, use_long_dictionary));
+        resolve(can_place_word_safely(board_from_array(board), convert_word_to_array(upper), row, col, direction, letters, valid_words_set) != null);
+    });
+}
+
+/**
+ * Finds every letter on the `board` that isn't part of a horizontal or vertical word of at least two letters
+ * @param board `Board` being checked
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @returns Array of the `(row, col)` of each orphaned letter; empty if there are none
+ */
+function find_orphan_letters(board: Board, min_col: number, max_col: number, min_row: number, max_row: number) {
+    const orphans: Array<[number, number]> = [];
+    for (let row=min_row; row<max_row+1; row++) {
+        for (let col=min_col; col<max_col+1; col++) {
+            if (board.get_val(row, col) != EMPTY_VALUE) {
+                const has_neighbor = (col > 0 && board.get_val(row, col-1) != EMPTY_VALUE) || (col < BOARD_SIZE-1 && board.get_val(row, col+1) != EMPTY_VALUE) || (row > 0 && board.get_val(row-1, col) != EMPTY_VALUE) || (row < BOARD_SIZE-1 && board.get_val(row+1, col) != EMPTY_VALUE);
+                if (!has_neighbor) {
+                    orphans.push([row, col]);
+                }
+            }
+        }
+    }
+    return orphans;
+}
+
+/**
+ * Finds every pair of tiles that touch only diagonally, i.e. with both of the cells next to both tiles empty
+ *
+ * Every such pair breaks the rule, since the words through one tile can only share a tile with the words through the other at one of those cells.
+ * @param board `Board` being checked
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @returns Array of the `(row, col)` of the upper and lower tile of each pair; empty if there are none
+ */
+function find_diagonal_touches(board: Board, min_col: number, max_col: number, min_row: number, max_row: number) {
+    const touches: Array<[[number, number], [number, number]]> = [];
+    for (let row=min_row; row<max_row; row++) {
+        for (let col=min_col; col<max_col+1; col++) {
+            if (board.get_val(row, col) == EMPTY_VALUE) {
+                continue;
+            }
+            for (const other_col of [col-1, col+1]) {
+                // Tiles joined through either of the cells beside them are in crossing words
+                if (other_col < min_col || other_col > max_col || board.get_val(row+1, other_col) == EMPTY_VALUE || board.get_val(row, other_col) != EMPTY_VALUE || board.get_val(row+1, col) != EMPTY_VALUE) {
+                    continue;
+                }
+                touches.push([[row, col], [row+1, other_col]]);
+            }
+        }
+    }
+    return touches;
+}
+
+/**
+ * Finds every place where two tiles touch only diagonally, so that their words can't cross each other there, which some variants of Bananagrams forbid (see `SolverConfig.strict_adjacency`)
+ * @param board The board array (e.g. `GameState.board`)
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @returns Array of the `(row, col)` of the upper and lower tile of each diagonal touch; empty if there are none
+ */
+export function diagonal_touches(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number) {
+    return find_diagonal_touches(board_from_array(board), min_col, max_col, min_row, max_row);
+}
+
+/**
+ * Mutable bookkeeping shared by every level of a single recursive search
+ */
+interface SearchContext {
+    /**
+     * Options for the search
+     */
+    config: SolverConfig,
+    /**
+     * Number of words that have been tried so far
+     */
+    words_checked: number,
+    /**
+     * The fewest letters left in the hand at any point of the search
+     */
+    fewest_remaining: number,
+    /**
+     * Whether `config.max_words_to_check` has run out (in which case the search unwinds by returning `null`)
+     */
+    exhausted: boolean,
+    /**
+     * Whether any placement was skipped because the board already reached the edge of the board array on that side (see `blocked_by_grid_edge`)
+     */
+    reached_edge: boolean,
+    /**
+     * How many times the search gave up on a board early because a letter left in the hand isn't in any of the words being tried
+     */
+    pruned: number,
+    /**
+     * How many times the search gave up on a board because of `config.vowel_prune`
+     */
+    vowel_prunes: number,
+    /**
+     * The furthest board reached from the current opening word, if `config.collect_opening_boards` is set
+     */
+    opening_best?: OpeningBoard,
+    /**
+     * The best board reached that hasn't been posted yet, if `config.stream_best_boards` is set
+     */
+    unposted_best?: BestBoard,
+    /**
+     * Called with the best board reached so far, if `config.stream_best_boards` is set
+     */
+    on_best_board?: (best_board: BestBoard) => void,
+    /**
+     * When a best board was last posted, in milliseconds since the epoch
+     */
+    last_posted: number,
+    /**
+     * How many times the search has backed away from each word after playing it at each depth, for the current opening word (see `config.max_backtracks_per_word`)
+     */
+    backtracks: Array<Map<Uint8Array, number>>,
+    /**
+     * Buffers reused by every play in the search
+     */
+    scratch: SearchScratch
+}
+
+/**
+ * Buffers reused across a whole search so that trying a word doesn't allocate; each depth has its own, since a play's buffers are in use until it's undone
+ */
+interface SearchScratch {
+    /**
+     * The letters left in the hand after the play at each depth
+     */
+    letters: Uint8Array[],
+    /**
+     * The indices played at each depth
+     */
+    played_indices: Array<Array<[number, number]>>
+}
+
+/**
+ * Gets the buffers for a depth of the search, creating them the first time the depth is reached
+ * @param scratch The search's buffers
+ * @param depth Depth of the play that will use the buffers
+ * @returns The remaining letters and played indices buffers for `depth`
+ */
+function scratch_at(scratch: SearchScratch, depth: number): [Uint8Array, Array<[number, number]>] {
+    while (scratch.letters.length <= depth) {
+        scratch.letters.push(new Uint8Array(26));
+        scratch.played_indices.push([]);
+    }
+    return [scratch.letters[depth], scratch.played_indices[depth]];
+}
+
+/**
+ * Gets how many times the search has backed away from each word at a depth, creating the count the first time the depth is reached
+ * @param backtracks The search's counts (see `SearchContext.backtracks`)
+ * @param depth Depth of the plays being counted
+ * @returns The number of times each word played at `depth` has been backed away from
+ */
+function backtracks_at(backtracks: Array<Map<Uint8Array, number>>, depth: number) {
+    while (backtracks.length <= depth) {
+        backtracks.push(new Map());
+    }
+    return backtracks[depth];
+}
+
+/**
+ * Creates a fresh `SearchContext`
+ * @param letters Length-26 array of the number of each letter in the hand when the search begins
+ * @param config Options for the search
+ * @param on_best_board Called with the best board reached so far, if `config.stream_best_boards` is set
+ * @returns A new `SearchContext` with nothing yet checked
+ */
+function new_search_context(letters: Uint8Array, config: SolverConfig, on_best_board?: (best_board: BestBoard) => void): SearchContext {
+    return {
+        config: config,
+        on_best_board: on_best_board,
+        words_checked: 0,
+        fewest_remaining: letters.reduce((a, b) => a + b, 0),
+        exhausted: false,
+        reached_edge: false,
+        pruned: 0,
+        vowel_prunes: 0,
+        last_posted: 0,
+        backtracks: [],
+        scratch: {letters: [], played_indices: []}
+    };
+}
+
+/**
+ * Counts the letters that must be on the solved board
+ * @param config Options for the solver
+ * @returns Length-26 array of the number of each letter in `config.required_letters`
+ */
+function required_letter_counts(config: SolverConfig) {
+    const required = new Uint8Array(26);
+    convert_word_to_array((config.required_letters ?? "").toUpperCase()).forEach(letter => required[letter] += 1);
+    return required;
+}
+
+/**
+ * Checks whether a complete board meets every acceptance criterion in the solver's options
+ * @param board The complete `Board`
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @param config Options for the search
+ * @returns Whether the board can be returned as a solution
+ */
+function is_acceptable_solution(board: Board, min_col: number, max_col: number, min_row: number, max_row: number, config: SolverConfig) {
+    if (config.min_width != null && max_col - min_col + 1 < config.min_width) {
+        return false;
+    }
+    if (config.min_height != null && max_row - min_row + 1 < config.min_height) {
+        return false;
+    }
+    if (config.max_area != null && (max_col - min_col + 1)*(max_row - min_row + 1) > config.max_area) {
+        return false;
+    }
+    // The search never plays a two-letter word with this set, but one can still form where words cross, or be on a reused previous board (see `is_acceptable_reused_board`)
+    if (config.no_two_letter_words && extract_placed_words(board, min_col, max_col, min_row, max_row).some(placed => placed.word.length === 2)) {
+        return false;
+    }
+    if (config.min_intersection_density != null && config.min_intersection_density > 0) {
+        const score = solution_score(board, min_col, max_col, min_row, max_row);
+        if (score.intersection_count/score.tile_count < config.min_intersection_density) {
+            return false;
+        }
+    }
+    // Forbidden words are left out of the dictionary the search uses, so this only catches them on a reused previous board (see `is_acceptable_reused_board`) or a partial board from the user
+    const forbidden = forbidden_words(config);
+    if (forbidden.length > 0) {
+        const forbidden_set = new Set(forbidden);
+        if (extract_placed_words(board, min_col, max_col, min_row, max_row).some(placed => forbidden_set.has(placed.word))) {
+            return false;
+        }
+    }
+    if (config.required_letters != null && config.required_letters.length > 0) {
+        const required = required_letter_counts(config);
+        const on_board = count_board_letters(board.arr, min_col, max_col, min_row, max_row);
+        if (required.some((count, i) => on_board[i] < count)) {
+            return false;
+        }
+    }
+    if (config.strict_adjacency && find_diagonal_touches(board, min_col, max_col, min_row, max_row).length > 0) {
+        return false;
+    }
+    // In strict mode, every letter must be part of a real word; only a partial board from the user can break this
+    return !config.strict || find_orphan_letters(board, min_col, max_col, min_row, max_row).length === 0;
+}
+
+/**
+ * Checks whether a valid placement meets the hard constraints in the solver's options
+ * @param board `Board` with the word played on it
+ * @param word The word played
+ * @param played_indices The indices at which new letters were played (see `play_word`)
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @param remaining_tiles How many tiles are left in the hand after the placement
+ * @param shared_needed How many letters the word must share with the tiles already on the board (see `required_intersections`)
+ * @param config Options for the search
+ * @returns Whether the search can continue from the placement
+ */
+function is_acceptable_placement(board: Board, word: Uint8Array, played_indices: Array<[number, number]>, min_col: number, max_col: number, min_row: number, max_row: number, remaining_tiles: number, shared_needed: number, config: SolverConfig) {
+    if (config.intersection_mode === "hard" && word.length - played_indices.length < shared_needed) {
+        return false;
+    }
+    if (config.min_intersection_density != null && config.min_intersection_density > 0) {
+        // Each tile still to be played can make at most itself and its four neighbors part of both a horizontal and a vertical word
+        const score = solution_score(board, min_col, max_col, min_row, max_row);
+        if (score.intersection_count + 5*remaining_tiles < config.min_intersection_density*(score.tile_count + remaining_tiles)) {
+            return false;
+        }
+    }
+    // Incidental words could repeat another word too
+    if (config.no_duplicate_words && !all_words_unique_on_board(board, min_col, max_col, min_row, max_row)) {
+        return false;
+    }
+    return config.max_word_count == null || extract_placed_words(board, min_col, max_col, min_row, max_row).length <= config.max_word_count;
+}
+
+/**
+ * Checks whether a board that was reused from the previous solve, rather than built by the search, can be returned as a solution
+ *
+ * The search checks each placement as it goes (see `is_acceptable_placement`), but the previous board may have been solved with different options.
+ * @param board The complete `Board`
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @param config Options for the solver
+ * @returns Whether the board meets every criterion in `config`
+ */
+function is_acceptable_reused_board(board: Board, min_col: number, max_col: number, min_row: number, max_row: number, config: SolverConfig) {
+    if (config.no_duplicate_words && !all_words_unique_on_board(board, min_col, max_col, min_row, max_row)) {
+        return false;
+    }
+    if (config.max_word_count != null && extract_placed_words(board, min_col, max_col, min_row, max_row).length > config.max_word_count) {
+        return false;
+    }
+    return is_acceptable_solution(board, min_col, max_col, min_row, max_row, config);
+}
+
+/**
+ * Shortest time between posts of the best board found so far (see `SolverConfig.stream_best_boards`)
+ */
+const STREAM_INTERVAL_MS = 1000;
+
+/**
+ * Copies the state of a search in progress, e.g. to post it from the worker
+ * @param board The `Board` being built
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @param remaining_letters Length-26 array of the number of each letter still in the hand
+ * @param play_sequence The words played so far
+ * @returns A `GameState` sharing nothing with the search, whose letters are the whole hand (those on `board` and those remaining)
+ */
+function snapshot_state(board: Board, min_col: number, max_col: number, min_row: number, max_row: number, remaining_letters: Uint8Array, play_sequence: PlaySequence): GameState {
+    const letters = count_board_letters(board.arr, min_col, max_col, min_row, max_row).map((count, i) => count + remaining_letters[i]);
+    return {
+        board: Uint8Array.from(board.arr),
+        min_col: min_col,
+        max_col: max_col,
+        min_row: min_row,
+        max_row: max_row,
+        letters: letters,
+        play_sequence: play_sequence.map<PlaySequence[number]>(([word, position]) => [Uint8Array.from(word), position])
+    };
+}
+
+/**
+ * Converts a best board posted by the solver into a solution, so that it can be kept as the result when the user settles for it
+ * @param best_board The best board posted by the solver (see `SolverConfig.stream_best_boards`)
+ * @param start When solving started
+ * @returns The solution for the partial board; its hand still includes the letters that weren't played (see `unused_letters`)
+ */
+export function best_board_solution(best_board: BestBoard, start: Date) {
+    const state = best_board.state;
+    return build_solution(board_from_array(state.board), state.min_col, state.max_col, state.min_row, state.max_row, state.letters, state.play_sequence, new Set(), start, best_board.words_checked);
+}
+
+/**
+ * Passes the best board found so far to `ctx.on_best_board`, unless one was passed too recently; if so, it's passed by a later call instead
+ * @param ctx Bookkeeping for the search (is modified in-place)
+ */
+function post_best_board(ctx: SearchContext) {
+    const now = Date.now();
+    if (ctx.unposted_best != null && now - ctx.last_posted >= STREAM_INTERVAL_MS) {
+        ctx.on_best_board?.(ctx.unposted_best);
+        ctx.unposted_best = undefined;
+        ctx.last_posted = now;
+    }
+}
+
+/**
+ * Passes the best board found so far to `ctx.on_best_board` if it hasn't been passed yet, however recently the last one was
+ * @param ctx Bookkeeping for the search (is modified in-place)
+ */
+function flush_best_board(ctx: SearchContext) {
+    if (ctx.unposted_best != null) {
+        ctx.on_best_board?.(ctx.unposted_best);
+        ctx.unposted_best = undefined;
+        ctx.last_posted = Date.now();
+    }
+}
+
+/**
+ * Counts another word as checked, and checks whether the search's budget has run out
+ * @param ctx Bookkeeping for the search (is modified in-place)
+ * @returns Whether the search can continue
+ */
+function check_budget(ctx: SearchContext) {
+    ctx.words_checked += 1;
+    if (ctx.unposted_best != null) {
+        post_best_board(ctx);
+    }
+    if (ctx.config.max_words_to_check != null && ctx.words_checked > ctx.config.max_words_to_check) {
+        ctx.exhausted = true;
+    }
+    return !ctx.exhausted;
+}
+
+/**
+ * Tries to play a word at a single location, recursing further if the play is valid
+ * @param board The `Board` to modify in-place
+ * @param word The word to play
+ * @param row_idx The starting row at which to play the word
+ * @param col_idx The starting column at which to play the word
+ * @param direction The direction in which to play the word
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @param valid_words_vec Array of arrays, each representing a word (see `convert_word_to_array`)
+ * @param valid_words_set Set of hashed word arrays
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @param depth Depth of the current recursive call
+ * @param play_sequence Sequence of played words for this current run
+ * @param previous_play_sequence Sequence of played words for the previous run, if any
+ * @param ctx Bookkeeping for the whole search
+ * @returns Whether the board was solved, and the new minimum/maximum indices of the board, or `null` if the budget ran out (see `ctx.exhausted`); if the board wasn't solved, it is left as it was
+ */
+function try_play_word(board: Board, word: Uint8Array, row_idx: number, col_idx: number, direction: "horizontal"|"vertical", min_col: number, max_col: number, min_row: number, max_row: number, valid_words_vec: Array<Uint8Array>, valid_words_set: Set<number>, letters: Uint8Array, depth: number, play_sequence: PlaySequence, previous_play_sequence: PlaySequence, ctx: SearchContext): [boolean, number, number, number, number]|null {
+    if (ctx.config.no_duplicate_words && play_sequence.some(([played]) => array_equal(played, word))) {
+        return [false, min_col, max_col, min_row, max_row];
+    }
+    const res = play_word(word, row_idx, col_idx, board, direction, letters, ...scratch_at(ctx.scratch, depth));
+    if (res == null) {
+        // The word would run off the board here, but it may still fit elsewhere
+        if (blocked_by_grid_edge(word.length, row_idx, col_idx, direction, min_col, max_col, min_row, max_row)) {
+            ctx.reached_edge = true;
+        }
+        return [false, min_col, max_col, min_row, max_row];
+    }
+    else if (res[0]) {
+        // If the word was played successfully (i.e. it's not a complete overlap and it borders at least one existing tile), then check the validity of the new words it forms
+        const new_min_col = Math.min(min_col, col_idx);
+        // Maximums are inclusive, matching the bounds of the opening word
+        const new_max_col = direction === "horizontal" ? Math.max(max_col, col_idx+word.length-1) : Math.max(max_col, col_idx);
+        const new_min_row = Math.min(min_row, row_idx);
+        const new_max_row = direction === "horizontal" ? Math.max(max_row, row_idx) : Math.max(max_row, row_idx+word.length-1);
+        const remaining = res[2].reduce((a, b) => a + b, 0);
+        if (check_word_and_crossings(board, row_idx, col_idx, direction, res[1], valid_words_set, ctx.config.no_two_letter_words) && is_acceptable_placement(board, word, res[1], new_min_col, new_max_col, new_min_row, new_max_row, remaining, required_intersections(ctx.config, direction, play_sequence), ctx.config)) {
+            // If it's valid, go to the next recursive level (unless we've all the letters, at which point we're done)
+            play_sequence.push([word, [row_idx, col_idx, direction]]);
+            // A complete board the options reject isn't a usable best board, so it's neither kept nor posted
+            const acceptable = res[3] !== "Finished" || is_acceptable_solution(board, new_min_col, new_max_col, new_min_row, new_max_row, ctx.config);
+            if (acceptable && remaining < ctx.fewest_remaining) {
+                ctx.fewest_remaining = remaining;
+                if (ctx.config.stream_best_boards && ctx.on_best_board != null) {
+                    // Copied now, between plays, so that the posted board is always a consistent one
+                    ctx.unposted_best = {board: board_to_vec(board, new_min_col, new_max_col, new_min_row, new_max_row, new Set()), leftover_letters: remaining, words_checked: ctx.words_checked, state: snapshot_state(board, new_min_col, new_max_col, new_min_row, new_max_row, res[2], play_sequence)};
+                    post_best_board(ctx);
+                }
+            }
+            if (acceptable && ctx.opening_best != null && remaining < ctx.opening_best.leftover_letters) {
+                ctx.opening_best = {word: ctx.opening_best.word, board: board_to_vec(board, new_min_col, new_max_col, new_min_row, new_max_row, new Set()), leftover_letters: remaining};
+            }
+            if (res[3] === "Finished") {
+                // Keep searching if the board doesn't meet the requested criteria
+                if (acceptable) {
+                    return [true, new_min_col, new_max_col, new_min_row, new_max_row];
+                }
+                play_sequence.pop();
+                undo_play(board, res[1]);
+                const backtracks = backtracks_at(ctx.backtracks, depth);
+                backtracks.set(word, (backtracks.get(word) ?? 0) + 1);
+            }
+            else if (res[3] === "Remaining") {
+                const res2 = play_further(board, new_min_col, new_max_col, new_min_row, new_max_row, valid_words_vec, valid_words_set, res[2], depth+1, play_sequence, previous_play_sequence, ctx);
+                if (res2 == null) {
+                    return null;
+                }
+                else if (res2[0]) {
+                    // If that recursive stack finishes successfully, we're done! (could have used another Result or Option rather than a bool in the returned tuple, but oh well)
+                    return res2;
+                }
+                else {
+                    // Otherwise, undo the previous play (cloning the board before each play so we don't have to undo is *way* slower)
+                    play_sequence.pop();
+                    undo_play(board, res[1]);
+                    const backtracks = backtracks_at(ctx.backtracks, depth);
+                    backtracks.set(word, (backtracks.get(word) ?? 0) + 1);
+                }
+            }
+        }
+        else {
+            // If the play formed some invalid words (or too many words), undo the previous play
+            undo_play(board, res[1]);
+        }
+    }
+    else {
+        // If trying to play the board was invalid, undo the play
+        undo_play(board, res[1]);
+    }
+    return [false, min_col, max_col, min_row, max_row];
+}
+
+/**
+ * Recursively solves Bananagrams
+ * @param board The `Board` to modify in-place
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @param valid_words_vec Array of arrays, each representing a word (see `convert_word_to_array`)
+ * @param valid_words_set Set of hashed word arrays
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @param depth Depth of the current recursive call
+ * @param play_sequence Sequence of played words for this current run
+ * @param previous_play_sequence Sequence of played words for the previous run, if any
+ * @param ctx Bookkeeping for the whole search
+ * @returns Whether the word could be validly played, and the new minimum/maximum indices of the board, or `null` if the budget ran out (see `ctx.exhausted`)
+ */
+function play_further(board: Board, min_col: number, max_col: number, min_row: number, max_row: number, valid_words_vec: Array<Uint8Array>, valid_words_set: Set<number>, letters: Uint8Array, depth: number, play_sequence: PlaySequence, previous_play_sequence: PlaySequence, ctx: SearchContext): [boolean, number, number, number, number]|null {
+    if (depth+1 < previous_play_sequence.length) {
+        // Replay the previous game's move at this depth
+        const [word, [row_idx, col_idx, direction]] = previous_play_sequence[depth+1];
+        if (!check_budget(ctx)) {
+            return null;
+        }
+        const res = try_play_word(board, word, row_idx, col_idx, direction, min_col, max_col, min_row, max_row, valid_words_vec, valid_words_set, letters, depth, play_sequence, previous_play_sequence, ctx);
+        if (res == null || res[0]) {
+            return res;
+        }
+        return [false, min_col, max_col, min_row, max_row];
+    }
+    // Every tile left has to be played as part of some word, so there's no point searching if a letter isn't in any of them
+    if (!ctx.config.no_coverage_prune && (letter_mask(null, letters) & ~coverage_mask(valid_words_vec)) !== 0) {
+        ctx.pruned += 1;
+        return [false, min_col, max_col, min_row, max_row];
+    }
+    if (ctx.config.vowel_prune && !vowel_balance_plausible(letters, valid_words_vec)) {
+        ctx.vowel_prunes += 1;
+        return [false, min_col, max_col, min_row, max_row];
+    }
+    // If we're at an odd depth, play horizontally first (trying to alternate horizontal-vertical-horizontal as a heuristic to solve faster)
+    let directions: Array<"horizontal"|"vertical"> = depth % 2 == 1 ? ["horizontal", "vertical"] : ["vertical", "horizontal"];
+    if (ctx.config.prefer_square && max_col - min_col != max_row - min_row) {
+        // Grow the board along its shorter side
+        directions = max_col - min_col > max_row - min_row ? ["vertical", "horizontal"] : ["horizontal", "vertical"];
+    }
+    const backtracks = backtracks_at(ctx.backtracks, depth);
+    for (const direction of directions) {
+        for (const word of valid_words_vec) {
+            if (ctx.config.max_backtracks_per_word != null && (backtracks.get(word) ?? 0) > ctx.config.max_backtracks_per_word) {
+                // Give up on words that keep leading nowhere from this depth
+                continue;
+            }
+            if (!check_budget(ctx)) {
+                return null;
+            }
+            const starts: Iterable<[number, number]> = ctx.config.lookahead_tiebreak
+                ? lookahead_starts(board, word, direction, min_col, max_col, min_row, max_row, valid_words_vec, valid_words_set, letters, depth, play_sequence, ctx)
+                : ordered_starts(board, word, direction, min_col, max_col, min_row, max_row, play_sequence, ctx.config);
+            for (const [row_idx, col_idx] of starts) {
+                const res = try_play_word(board, word, row_idx, col_idx, direction, min_col, max_col, min_row, max_row, valid_words_vec, valid_words_set, letters, depth, play_sequence, previous_play_sequence, ctx);
+                if (res == null || res[0]) {
+                    return res;
+                }
+            }
+        }
+    }
+    return [false, min_col, max_col, min_row, max_row];
+}
+
+/**
+ * Tries to play a single letter on the board
+ * @param board 
+ * @param min_col The `Board` on which to try to play the `letter`
+ * @param max_col Minimum occupied column index in `board`
+ * @param min_row Maximum occupied column index in `board`
+ * @param max_row Minimum occupied row index in `board`
+ * @param letter Maximum occupied row index in `board`
+ * @param valid_words_set Set of all valid hashed words
+ * @param no_two_letter_words Whether two-letter words are invalid
+ * @returns Either `null` if no solution was found, or `(row, col, new_min_col, new_max_col, new_min_row, new_max_row)` on success
+ */
+function play_one_letter(board: Board, min_col: number, max_col: number, min_row: number, max_row: number, letter: number, valid_words_set: Set<number>, no_two_letter_words: boolean): [number, number, number, number, number, number]|null {
+    // Loop through all possible locations and check if the letter works there
+    for (let row=min_row-1; row<max_row+2; row++) {
+        for (let col=min_col-1; col<max_col+2; col++) {
+            if (row < BOARD_SIZE && col < BOARD_SIZE && board.get_val(row, col) == EMPTY_VALUE) {   // row/col don't need to be checked if they're greater than 0 since they'd underflow
+                if ((col > 0 && board.get_val(row, col-1) != EMPTY_VALUE) || (col < BOARD_SIZE-1 && board.get_val(row, col+1) != EMPTY_VALUE) || (row > 0 && board.get_val(row-1, col) != EMPTY_VALUE) || (row < BOARD_SIZE-1 && board.get_val(row+1, col) != EMPTY_VALUE)) {
+                    board.set_val(row, col, letter);
+                    const new_min_col = Math.min(min_col, col);
+                    const new_max_col = Math.max(max_col, col);
+                    const new_min_row = Math.min(min_row, row);
+                    const new_max_row = Math.max(max_row, row);
+                    // Could also check vertically, since the crossing word is checked either way
+                    if (check_word_and_crossings(board, row, col, "horizontal", [[row, col]], valid_words_set, no_two_letter_words)) {
+                        // If it's valid, return the (potentially) new bounds, along with the location the letter was played
+                        return [row, col, new_min_col, new_max_col, new_min_row, new_max_row];
+                    }
+                    else {
+                        // If the board wasn't ok, reset this spot
+                        board.set_val(row, col, EMPTY_VALUE);
+                    }
+                }
+            }
+        }
+    }
+    // Return `null` if we don't find a solution
+    return null;
+}
+
+/// Attempts to play off an existing board
+/// # Arguments
+/// * `previous_play_sequence` - Sequence of previous played moves
+/// * `valid_words_vec` - Vector of valid words for the given hand of letters
+/// * `valid_words_set` - HashSet of valid words (HashSet of `valid_words_vec` for faster membership checking)
+/// * `letters` - Array of the number of each letter in the hand
+/// * `ctx` - Bookkeeping for the search
+/// # Returns
+/// `Option` with:
+/// * `Board` - updated board
+/// * `PlaySequence` - updated play sequence
+/// * `usize` - Minimum occupied column index in `board`
+/// * `usize` - Maximum occupied column index in `board`
+/// * `usize` - Minimum occupied row index in `board`
+/// * `usize` - Maximum occupied row index in `board`
+/// 
+/// *or `None` if no valid play can be made on the existing board*
+function play_existing(previous_play_sequence: PlaySequence, valid_words_vec: Array<Uint8Array>, valid_words_set: Set<number>, letters: Uint8Array, ctx: SearchContext): [Board, PlaySequence, number, number, number, number]|null {
+    const board = new Board();
+    const row = previous_play_sequence[0][1][0];
+    const col_start = previous_play_sequence[0][1][1];
+    const word = previous_play_sequence[0][0];
+    const use_letters = Uint8Array.from(letters);
+    const word_letters = new Set<number>();
+    for (let i=0; i<word.length; i++) {
+        board.set_val(row, col_start+i, word[i]);
+        use_letters[word[i]] -= 1;
+        word_letters.add(word[i]);
+    }
+    const min_col = col_start;
+    const min_row = row;
+    const max_col = col_start + (word.length-1);
+    const max_row = row;
+    const play_sequence: PlaySequence = [];
+    play_sequence.push([word, [row, col_start, "horizontal"]]);
+    if (use_letters.every(count => count == 0)) {
+        if (is_acceptable_solution(board, min_col, max_col, min_row, max_row, ctx.config)) {
+            return [board, play_sequence, min_col, max_col, min_row, max_row];
+        }
+        return null;
+    }
+    else {
+        const new_valid_words_vec = valid_words_vec.filter(word => check_filter_after_play(use_letters, word, word_letters, ctx.config.filter_letters_on_board ?? DEFAULT_FILTER_LETTERS_ON_BOARD));
+        const res = play_further(board, min_col, max_col, min_row, max_row, new_valid_words_vec, valid_words_set, use_letters, 0, play_sequence, previous_play_sequence, ctx);
+        if (res == null) {
+            return null;
+        }
+        else {
+            if (res[0]) {
+                return [board, play_sequence, res[1], res[2], res[3], res[4]];
+            }
+            else {
+                return null;
+            }
+        }
+    }
+}
+
+/**
+ * Measures how spread out the tiles on a board are
+ * @param board `Board` to measure
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @returns The sum of the squared distances of every tile from the centroid of all the tiles; lower is more compact
+ */
+export function board_spread(board: Board, min_col: number, max_col: number, min_row: number, max_row: number) {
+    let count = 0;
+    let row_sum = 0;
+    let col_sum = 0;
+    let squared_sum = 0;
+    for (let row=min_row; row<max_row+1; row++) {
+        for (let col=min_col; col<max_col+1; col++) {
+            if (board.get_val(row, col) != EMPTY_VALUE) {
+                count += 1;
+                row_sum += row;
+                col_sum += col;
+                squared_sum += row*row + col*col;
+            }
+        }
+    }
+    return count === 0 ? 0 : squared_sum - (row_sum*row_sum + col_sum*col_sum)/count;
+}
+
+/**
+ * Shrinks bounds to the tiles actually on the board
+ * @param board `Board` to check
+ * @param min_col Minimum column index that may be occupied in `board`
+ * @param max_col Maximum column index that may be occupied in `board`
+ * @param min_row Minimum row index that may be occupied in `board`
+ * @param max_row Maximum row index that may be occupied in `board`
+ * @returns The minimum occupied column index, maximum occupied column index, minimum occupied row index, and maximum occupied row index
+ */
+function occupied_bounds(board: Board, min_col: number, max_col: number, min_row: number, max_row: number): [number, number, number, number] {
+    let new_min_col = max_col;
+    let new_max_col = min_col;
+    let new_min_row = max_row;
+    let new_max_row = min_row;
+    for (let row=min_row; row<max_row+1; row++) {
+        for (let col=min_col; col<max_col+1; col++) {
+            if (board.get_val(row, col) != EMPTY_VALUE) {
+                new_min_col = Math.min(new_min_col, col);
+                new_max_col = Math.max(new_max_col, col);
+                new_min_row = Math.min(new_min_row, row);
+                new_max_row = Math.max(new_max_row, row);
+            }
+        }
+    }
+    return [new_min_col, new_max_col, new_min_row, new_max_row];
+}
+
+/**
+ * Checks whether every tile on the board can be reached from every other
+ * @param board `Board` to check
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @returns Whether the tiles form a single connected group
+ */
+export function is_connected(board: Board, min_col: number, max_col: number, min_row: number, max_row: number) {
+    const tiles: Array<[number, number]> = [];
+    for (let row=min_row; row<max_row+1; row++) {
+        for (let col=min_col; col<max_col+1; col++) {
+            if (board.get_val(row, col) != EMPTY_VALUE) {
+                tiles.push([row, col]);
+            }
+        }
+    }
+    if (tiles.length === 0) {
+        return true;
+    }
+    const seen = new Set<number>([vec_hasher(tiles[0])]);
+    const to_visit = [tiles[0]];
+    while (to_visit.length > 0) {
+        const [row, col] = to_visit.pop()!;
+        for (const [r, c] of [[row-1, col], [row+1, col], [row, col-1], [row, col+1]]) {
+            if (r >= min_row && r <= max_row && c >= min_col && c <= max_col && board.get_val(r, c) != EMPTY_VALUE && !seen.has(vec_hasher([r, c]))) {
+                seen.add(vec_hasher([r, c]));
+                to_visit.push([r, c]);
+            }
+        }
+    }
+    return seen.size === tiles.length;
+}
+
+/**
+ * Gets the tiles of a played word that aren't part of any word in the other direction, i.e. those that could be picked up without breaking a crossing word
+ * @param board `Board` the word is on
+ * @param word The word
+ * @param row_idx The starting row of the word
+ * @param col_idx The starting column of the word
+ * @param direction The direction the word is played in
+ * @param locked Hashed indices of tiles that must never be picked up (see `locked_word_cells`)
+ * @returns The indices of the removable tiles
+ */
+function get_removable_indices(board: Board, word: Uint8Array, row_idx: number, col_idx: number, direction: "horizontal"|"vertical", locked: Set<number>) {
+    const removable: Array<[number, number]> = [];
+    for (let i=0; i<word.length; i++) {
+        const row = direction === "horizontal" ? row_idx : row_idx+i;
+        const col = direction === "horizontal" ? col_idx+i : col_idx;
+        const crossed = direction === "horizontal" ?
+            board.get_val(row-1, col) != EMPTY_VALUE || board.get_val(row+1, col) != EMPTY_VALUE :
+            board.get_val(row, col-1) != EMPTY_VALUE || board.get_val(row, col+1) != EMPTY_VALUE;
+        if (!crossed && !locked.has(vec_hasher([row, col]))) {
+            removable.push([row, col]);
+        }
+    }
+    return removable;
+}
+
+/**
+ * A board with a word taken off of it
+ */
+export interface RemovedWord {
+    /**
+     * The new board array (the original isn't modified)
+     */
+    board: Uint8Array,
+    /**
+     * The new minimum occupied column index
+     */
+    min_col: number,
+    /**
+     * The new maximum occupied column index
+     */
+    max_col: number,
+    /**
+     * The new minimum occupied row index
+     */
+    min_row: number,
+    /**
+     * The new maximum occupied row index
+     */
+    max_row: number,
+    /**
+     * Mapping of each uppercase Latin character to the number of that letter picked up (letters shared with crossing words stay on the board)
+     */
+    freed: Map<string, number>
+}
+
+/**
+ * Async function to take a word off of a board, picking up only the tiles that aren't part of a crossing word, e.g. when editing a board by hand
+ * @param board The board array (e.g. `GameState.board`); not modified
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @param word The word to remove
+ * @param row The starting row of the word
+ * @param col The starting column of the word
+ * @param direction The direction the word is played in
+ * @returns The board without the word, and the letters picked up
+ */
+export async function remove_word_from_board(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number, word: string, row: number, col: number, direction: "horizontal"|"vertical") {
+    return new Promise<RemovedWord>((resolve, reject) => {
+        const upper = word.toUpperCase();
+        if (!/^[A-Z]+$/.test(upper)) {
+            reject("Words may only contain the letters A-Z");
+            return;
+        }
+        if (row < 0 || col < 0 || row >= BOARD_SIZE || col >= BOARD_SIZE) {
+            reject("The position (" + row + ", " + col + ") is not on the board");
+            return;
+        }
+        const word_arr = convert_word_to_array(upper);
+        const wrapped = board_from_array(Uint8Array.from(board));
+        // The word must be the whole run starting at the position, not just part of a longer one
+        const before_empty = direction === "horizontal" ? col == 0 || wrapped.get_val(row, col-1) == EMPTY_VALUE : row == 0 || wrapped.get_val(row-1, col) == EMPTY_VALUE;
+        if (!before_empty || !array_equal(Uint8Array.from(get_run_through(wrapped, row, col, direction)), word_arr)) {
+            reject(upper + " is not at (" + row + ", " + col + ") going " + direction);
+            return;
+        }
+        const freed = new Map<string, number>();
+        for (const [r, c] of get_removable_indices(wrapped, word_arr, row, col, direction, new Set())) {
+            const letter = UPPERCASE[wrapped.get_val(r, c)];
+            freed.set(letter, (freed.get(letter) ?? 0) + 1);
+            wrapped.set_val(r, c, EMPTY_VALUE);
+        }
+        const [new_min_col, new_max_col, new_min_row, new_max_row] = occupied_bounds(wrapped, min_col, max_col, min_row, max_row);
+        resolve({board: wrapped.arr, min_col: new_min_col, max_col: new_max_col, min_row: new_min_row, max_row: new_max_row, freed: freed});
+    });
+}
+
+/**
+ * Whether a board is still valid after it was edited (see `BoardEditor`)
+ */
+export interface EditValidation {
+    /**
+     * Whether the board is connected and every word on it is in the dictionary
+     */
+    valid: boolean,
+    /**
+     * Whether every tile on the board can be reached from every other
+     */
+    connected: boolean,
+    /**
+     * Every word on the board that isn't in the dictionary (see `validate_board`)
+     */
+    invalid_words: InvalidWord[]
+}
+
+/**
+ * The result of taking a single tile off of a board (see `BoardEditor.remove_letter`)
+ */
+export interface RemovedLetter extends EditValidation {
+    /**
+     * The letter picked up
+     */
+    freed: string
+}
+
+/**
+ * A board being edited by hand a tile at a time, checked after every change so mistakes can be shown as they're made
+ */
+export class BoardEditor {
+    /**
+     * The board being edited
+     */
+    private board: Board
+    /**
+     * Length-26 array of the number of each letter on the board
+     */
+    letters_on_board: Uint8Array
+    /**
+     * Minimum occupied column index
+     */
+    min_col: number
+    /**
+     * Maximum occupied column index
+     */
+    max_col: number
+    /**
+     * Minimum occupied row index
+     */
+    min_row: number
+    /**
+     * Maximum occupied row index
+     */
+    max_row: number
+    /**
+     * Set of all valid hashed words to check against
+     */
+    private valid_words_set: Set<number>
+    /**
+     * Starts editing a copy of a board
+     * @param board The board array (e.g. `GameState.board`); not modified
+     * @param min_col Minimum occupied column index
+     * @param max_col Maximum occupied column index
+     * @param min_row Minimum occupied row index
+     * @param max_row Maximum occupied row index
+     * @param state Current state of the app
+     * @param use_long_dictionary Whether to check against the full Scrabble dictionary rather than just the common words
+     * @throws An error message if the requested dictionary isn't in this build (see `dictionary_capabilities`)
+     */
+    constructor(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number, state: AppState, use_long_dictionary: boolean) {
+        this.board = board_from_array(Uint8Array.from(board));
+        this.letters_on_board = count_board_letters(board, min_col, max_col, min_row, max_row);
+        this.min_col = min_col;
+        this.max_col = max_col;
+        this.min_row = min_row;
+        this.max_row = max_row;
+        const unavailable = dictionary_unavailable(use_long_dictionary);
+        if (unavailable != null) {
+            throw unavailable;
+        }
+        this.valid_words_set = use_long_dictionary ? dictionary_set(state.all_words_long) : state.all_words_short_set;
+    }
+    /**
+     * Puts a tile on an empty cell
+     * @param row Row of the cell
+     * @param col Column of the cell
+     * @param letter The letter of the tile
+     * @returns Whether the board is still valid, or an error message if the tile couldn't be placed
+     */
+    place_letter(row: number, col: number, letter: string): EditValidation|string {
+        const upper = letter.toUpperCase();
+        if (!/^[A-Z]$/.test(upper)) {
+            return "Tiles must be a single letter A-Z";
+        }
+        if (row < 0 || col < 0 || row >= BOARD_SIZE || col >= BOARD_SIZE) {
+            return "The position (" + row + ", " + col + ") is not on the board";
+        }
+        if (this.board.get_val(row, col) != EMPTY_VALUE) {
+            return "There's already a tile at (" + row + ", " + col + ")";
+        }
+        if (this.letters_on_board.every(count => count === 0)) {
+            [this.min_col, this.max_col, this.min_row, this.max_row] = [col, col, row, row];
+        }
+        else {
+            [this.min_col, this.max_col, this.min_row, this.max_row] = [Math.min(this.min_col, col), Math.max(this.max_col, col), Math.min(this.min_row, row), Math.max(this.max_row, row)];
+        }
+        const value = upper.charCodeAt(0) - 65;
+        this.board.set_val(row, col, value);
+        this.letters_on_board[value] += 1;
+        return this.validate();
+    }
+    /**
+     * Takes the tile off of a cell
+     * @param row Row of the cell
+     * @param col Column of the cell
+     * @returns The letter picked up and whether the board is still valid, or an error message if the cell is empty
+     */
+    remove_letter(row: number, col: number): RemovedLetter|string {
+        if (row < 0 || col < 0 || row >= BOARD_SIZE || col >= BOARD_SIZE || this.board.get_val(row, col) == EMPTY_VALUE) {
+            return "There's no tile at (" + row + ", " + col + ")";
+        }
+        const value = this.board.get_val(row, col);
+        this.board.set_val(row, col, EMPTY_VALUE);
+        this.letters_on_board[value] -= 1;
+        [this.min_col, this.max_col, this.min_row, this.max_row] = occupied_bounds(this.board, this.min_col, this.max_col, this.min_row, this.max_row);
+        return {...this.validate(), freed: UPPERCASE[value]};
+    }
+    /**
+     * Gets a copy of the board being edited
+     * @returns The board array and its bounds
+     */
+    get_board(): Pick<GameState, "board"|"min_col"|"max_col"|"min_row"|"max_row"> {
+        return {board: Uint8Array.from(this.board.arr), min_col: this.min_col, max_col: this.max_col, min_row: this.min_row, max_row: this.max_row};
+    }
+    /**
+     * Checks the board being edited
+     * @returns Whether the board is connected and which of its words aren't in the dictionary
+     */
+    private validate(): EditValidation {
+        const connected = is_connected(this.board, this.min_col, this.max_col, this.min_row, this.max_row);
+        const invalid = invalid_words(this.board, this.min_col, this.max_col, this.min_row, this.max_row, this.valid_words_set);
+        return {valid: connected && invalid.length === 0, connected: connected, invalid_words: invalid};
+    }
+}
+
+/**
+ * A measure of a board used when moving words around it, where lower is better
+ */
+type board_cost_t = (board: Board, min_col: number, max_col: number, min_row: number, max_row: number) => number;
+
+/**
+ * Picks up the removable tiles of a word and tries to play the word again where it makes the board better
+ * @param board `Board` the word is on (is modified in-place if the word is moved)
+ * @param word The word
+ * @param row_idx The starting row of the word
+ * @param col_idx The starting column of the word
+ * @param direction The direction the word is played in
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @param valid_words_set Set of all valid hashed words
+ * @param config Options for the solver; the moved board must still be acceptable under them
+ * @param locked Hashed indices of tiles that must never be picked up (see `locked_word_cells`)
+ * @param cost Measure of the board to lower; the word is moved to wherever lowers it the most
+ * @param scratch `Board` to try placements on
+ * @returns The word's new starting row, column, and direction if it was moved, otherwise `null`
+ */
+function play_removing(board: Board, word: Uint8Array, row_idx: number, col_idx: number, direction: "horizontal"|"vertical", min_col: number, max_col: number, min_row: number, max_row: number, valid_words_set: Set<number>, config: SolverConfig, locked: Set<number>, cost: board_cost_t, scratch: Board): [number, number, "horizontal"|"vertical"]|null {
+    // Words played later may have replaced part of this one
+    for (let i=0; i<word.length; i++) {
+        if ((direction === "horizontal" ? board.get_val(row_idx, col_idx+i) : board.get_val(row_idx+i, col_idx)) != word[i]) {
+            return null;
+        }
+    }
+    const removable = get_removable_indices(board, word, row_idx, col_idx, direction, locked);
+    // If nothing can be picked up the word can't move, and if everything can then it's the only word on the board
+    if (removable.length === 0 || removable.length === word.length) {
+        return null;
+    }
+    const current_cost = cost(board, min_col, max_col, min_row, max_row);
+    const removed_letters = new Uint8Array(26);
+    const removed_values = removable.map(([row, col]) => board.get_val(row, col));
+    for (const value of removed_values) {
+        removed_letters[value] += 1;
+    }
+    undo_play(board, removable);
+    let best: [number, number, "horizontal"|"vertical", number]|null = null;
+    // The rest of the board must still be valid without the picked up tiles
+    if (is_connected(board, min_col, max_col, min_row, max_row) && extract_placed_words(board, min_col, max_col, min_row, max_row).every(placed => valid_words_set.has(vec_hasher(convert_word_to_array(placed.word))))) {
+        for (const new_direction of ["horizontal", "vertical"] as const) {
+            for (const [row, col] of candidate_starts(word.length, new_direction, min_col, max_col, min_row, max_row)) {
+                const res = can_place_word_safely(board, word, row, col, new_direction, removed_letters, valid_words_set, scratch);
+                // Every picked up tile must be used again
+                if (res == null || !res[1].every(count => count == 0)) {
+                    continue;
+                }
+                const [new_min_col, new_max_col, new_min_row, new_max_row] = occupied_bounds(scratch, Math.min(min_col, col), Math.max(max_col, new_direction === "horizontal" ? col+word.length-1 : col), Math.min(min_row, row), Math.max(max_row, new_direction === "horizontal" ? row : row+word.length-1));
+                const new_cost = cost(scratch, new_min_col, new_max_col, new_min_row, new_max_row);
+                if (new_cost < current_cost && (best == null || new_cost < best[3]) && is_acceptable_solution(scratch, new_min_col, new_max_col, new_min_row, new_max_row, config) && (config.max_word_count == null || extract_placed_words(scratch, new_min_col, new_max_col, new_min_row, new_max_row).length <= config.max_word_count)) {
+                    best = [row, col, new_direction, new_cost];
+                }
+            }
+        }
+    }
+    if (best == null) {
+        // Put the tiles back where they were
+        removable.forEach(([row, col], i) => board.set_val(row, col, removed_values[i]));
+        return null;
+    }
+    play_word(word, best[0], best[1], board, best[2], removed_letters);
+    return [best[0], best[1], best[2]];
+}
+
+/**
+ * Gets the tiles making up every occurrence of a word on the board
+ * @param board `Board` to search
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @param word The word to find
+ * @returns Hashed indices of the tiles of each occurrence of `word`
+ */
+function locked_word_cells(board: Board, min_col: number, max_col: number, min_row: number, max_row: number, word: string) {
+    const cells = new Set<number>();
+    for (const placed of extract_placed_words(board, min_col, max_col, min_row, max_row)) {
+        if (placed.word === word.toUpperCase()) {
+            for (let i=0; i<placed.word.length; i++) {
+                cells.add(vec_hasher(placed.direction === "horizontal" ? [placed.row, placed.col+i] : [placed.row+i, placed.col]));
+            }
+        }
+    }
+    return cells;
+}
+
+/**
+ * Hill-climbs towards a better board by repeatedly moving single words to wherever lowers `cost` the most, until no move lowers it or `max_rounds` words have been moved
+ * 
+ * The opening word is never moved. Moved words are moved to the end of `play_sequence`.
+ * @param board A solved `Board` (is modified in-place)
+ * @param min_col Minimum occupied column index in `board`
+ * @param max_col Maximum occupied column index in `board`
+ * @param min_row Minimum occupied row index in `board`
+ * @param max_row Maximum occupied row index in `board`
+ * @param play_sequence Sequence of played words that made `board` (is modified in-place)
+ * @param valid_words_set Set of all valid hashed words
+ * @param config Options for the solver
+ * @param cost Measure of the board to lower
+ * @param max_rounds Maximum number of words to move
+ * @returns The number of words moved, and the new minimum column index, maximum column index, minimum row index, and maximum row index
+ */
+export function hill_climb(board: Board, min_col: number, max_col: number, min_row: number, max_row: number, play_sequence: PlaySequence, valid_words_set: Set<number>, config: SolverConfig, cost: board_cost_t, max_rounds: number): [number, number, number, number, number] {
+    const scratch = new Board();
+    const locked = config.locked_word == null ? new Set<number>() : locked_word_cells(board, min_col, max_col, min_row, max_row, config.locked_word);
+    let rounds = 0;
+    let improved = true;
+    while (improved && rounds < max_rounds) {
+        improved = false;
+        for (let i=1; i<play_sequence.length; i++) {
+            const [word, [row_idx, col_idx, direction]] = play_sequence[i];
+            const moved = play_removing(board, word, row_idx, col_idx, direction, min_col, max_col, min_row, max_row, valid_words_set, config, locked, cost, scratch);
+            if (moved != null) {
+                play_sequence.splice(i, 1);
+                play_sequence.push([word, moved]);
+                [min_col, max_col, min_row, max_row] = occupied_bounds(board, Math.min(min_col, moved[1]), Math.max(max_col, moved[2] === "horizontal" ? moved[1]+word.length-1 : moved[1]), Math.min(min_row, moved[0]), Math.max(max_row, moved[2] === "horizontal" ? moved[0] : moved[0]+word.length-1));
+                rounds += 1;
+                improved = true;
+                break;
+            }
+        }
+    }
+    return [rounds, min_col, max_col, min_row, max_row];
+}
+
+/**
+ * Converts a mapping of letters from the frontend into the length-26 array used internally
+ * @param available_letters Mapping of string letters to numeric quantity of each letter
+ * @returns Length-26 array of the number of each letter, or an error message if a letter is missing or negative
+ */
+function letters_from_map(available_letters: Map<string, number>) {
+    const letters = new Uint8Array(26);
+    for (const c of UPPERCASE) {
+        const num = available_letters.get(c);
+        if (num != null) {
+            if (num < 0) {
+                return "Number of letter " + c + " is " + num + ", but must be greater than or equal to 0!";
+            }
+            letters[c.charCodeAt(0) - 65] = num;
+        }
+        else {
+            return "Missing letter: " + c;
+        }
+    }
+    return letters;
+}
+
+/**
+ * The hand as the solver sees it, for debugging
+ */
+export interface LettersDebug {
+    /**
+     * The number of each letter
+     */
+    letters: Record<string, number>,
+    /**
+     * The total number of letters
+     */
+    total: number,
+    /**
+     * Letters of which there are more than in standard Bananagrams
+     */
+    over_distribution: string[]
+}
+
+/**
+ * Async function to echo back a hand exactly as the solver parses it, to confirm the frontend built it correctly
+ * @param available_letters Mapping of string letters to numeric quantity of each letter
+ * @returns The number of each letter, the total, and which letters exceed the standard Bananagrams tile distribution
+ */
+export async function debug_letters(available_letters: Map<string, number>) {
+    return new Promise<LettersDebug>((resolve, reject) => {
+        const letters = letters_from_map(available_letters);
+        if (typeof letters === "string") {
+            reject(letters);
+            return;
+        }
+        const as_record: Record<string, number> = {};
+        const over_distribution: string[] = [];
+        for (let i=0; i<26; i++) {
+            as_record[UPPERCASE.charAt(i)] = letters[i];
+            if (letters[i] > REGULAR_TILES[i]) {
+                over_distribution.push(UPPERCASE.charAt(i));
+            }
+        }
+        resolve({letters: as_record, total: letters.reduce((a, b) => a + b, 0), over_distribution: over_distribution});
+    });
+}
+
+/**
+ * Which dictionaries this build of the app loads (see `dictionary_capabilities`)
+ */
+const BUILD_DICTIONARIES = import.meta.env.VITE_DICTIONARIES;
+
+/**
+ * Which dictionaries are available in a build of the app
+ */
+export interface DictionaryCapabilities {
+    /**
+     * Whether the common words are loaded; if not, `AppState.all_words_short` holds the full Scrabble dictionary instead, so that the solver still has words to use
+     */
+    short: boolean,
+    /**
+     * Whether the full Scrabble dictionary is loaded; if not, asking for it gives an error
+     */
+    long: boolean
+}
+
+/**
+ * Gets which dictionaries this build of the app includes, as chosen by the `VITE_DICTIONARIES` environment variable when building, e.g. so the frontend can hide choices that aren't available
+ * @returns Whether each dictionary is available
+ */
+export function dictionary_capabilities(): DictionaryCapabilities {
+    return {short: BUILD_DICTIONARIES !== "full", long: BUILD_DICTIONARIES !== "short"};
+}
+
+/**
+ * Checks whether the requested dictionary is in this build of the app
+ * @param use_long_dictionary Whether the full Scrabble dictionary was requested rather than the common words
+ * @returns An error message if the dictionary isn't available, otherwise `null`
+ */
+function dictionary_unavailable(use_long_dictionary: boolean) {
+    return use_long_dictionary && !dictionary_capabilities().long ? "The full Scrabble dictionary isn't available in this build" : null;
+}
+
+/**
+ * Gets the requested dictionary from the app state
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether to use the full Scrabble dictionary rather than the common words
+ * @returns Array of words in the requested dictionary
+ */
+function get_dictionary(state: AppState, use_long_dictionary: boolean) {
+    return use_long_dictionary ? state.all_words_long : state.all_words_short;
+}
+
+/**
+ * Problems found in a loaded dictionary
+ */
+export interface DictionaryHealth {
+    /**
+     * Number of entries in the dictionary
+     */
+    total: number,
+    /**
+     * Words that appear more than once (each listed once)
+     */
+    duplicates: string[],
+    /**
+     * Indices of entries with no letters left after conversion (see `convert_word_to_array`, which drops anything other than A-Z)
+     */
+    empty_indices: number[],
+    /**
+     * Words longer than `MAX_WORD_LENGTH`, which can't always be played
+     */
+    too_long: string[]
+}
+
+/**
+ * Async function to check a loaded dictionary for entries that won't behave as expected, e.g. to debug a custom word list
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether to check the full Scrabble dictionary rather than the common words
+ * @returns The problems found
+ */
+export async function dictionary_health(state: AppState, use_long_dictionary: boolean) {
+    return new Promise<DictionaryHealth>((resolve, reject) => {
+        const unavailable = dictionary_unavailable(use_long_dictionary);
+        if (unavailable != null) {
+            reject(unavailable);
+            return;
+        }
+        const dictionary = get_dictionary(state, use_long_dictionary);
+        const seen = new Set<string>();
+        const duplicates = new Set<string>();
+        const empty_indices: number[] = [];
+        const too_long: string[] = [];
+        dictionary.forEach((word, i) => {
+            if (word.length === 0) {
+                empty_indices.push(i);
+                return;
+            }
+            const as_string = convert_array_to_word(word);
+            if (word.length > MAX_WORD_LENGTH) {
+                too_long.push(as_string);
+            }
+            if (seen.has(as_string)) {
+                duplicates.add(as_string);
+            }
+            seen.add(as_string);
+        });
+        resolve({total: dictionary.length, duplicates: [...duplicates], empty_indices: empty_indices, too_long: too_long});
+    });
+}
+
+/**
+ * A single pattern in a hand that is likely to make it unsolvable
+ */
+export interface DeadEndPattern {
+    /**
+     * The letters involved in the pattern
+     */
+    letters: string,
+    /**
+     * Human-readable description of the problem
+     */
+    description: string
+}
+
+/**
+ * The result of checking a hand for common dead-end patterns
+ */
+export interface DeadEndAnalysis {
+    /**
+     * Every dead-end pattern found in the hand; empty if none were found
+     */
+    patterns: DeadEndPattern[],
+    /**
+     * Letters that would be good candidates to dump
+     */
+    suggested_dumps: string[]
+}
+
+/**
+ * Most words `can_play_every_copy` will try before giving up
+ */
+const COPY_SEARCH_LIMIT = 100000;
+
+/**
+ * Checks whether every copy of a letter in a hand can be played at once, in words that each cross (and so reuse one tile of) the words before them
+ * @param letter Index of the letter (from 0 for 'A' to 25 for 'Z')
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @param words The words that can be made from `letters` and contain `letter`
+ * @returns Whether some set of `words` plays every copy of `letter`, or `null` if the search gave up after `COPY_SEARCH_LIMIT` words
+ */
+function can_play_every_copy(letter: number, letters: Uint8Array, words: Array<Uint8Array>): boolean|null {
+    const word_counts = words.map(word => {
+        const counts = new Uint8Array(26);
+        word.forEach(l => counts[l] += 1);
+        return counts;
+    });
+    const on_board = new Uint8Array(26);
+    let tried = 0;
+    /**
+     * Tries each word next, recursively
+     * @param remaining Length-26 array of the letters not yet played
+     * @param first Whether no word has been played yet (so there's nothing to cross)
+     * @returns Whether the rest of the copies can be played, or `null` if the search gave up
+     */
+    const search = (remaining: Uint8Array, first: boolean): boolean|null => {
+        if (remaining[letter] === 0) {
+            return true;
+        }
+        for (const counts of word_counts) {
+            tried += 1;
+            if (tried > COPY_SEARCH_LIMIT) {
+                return null;
+            }
+            // At most one letter missing from the hand, which must already be on the board to be crossed
+            let crossed = -1;
+            let fits = true;
+            for (let i=0; i<26; i++) {
+                const missing = counts[i] - remaining[i];
+                if (missing > 0) {
+                    if (missing > 1 || crossed !== -1 || first || on_board[i] === 0) {
+                        fits = false;
+                        break;
+                    }
+                    crossed = i;
+                }
+            }
+            const used = counts.slice();
+            if (crossed !== -1) {
+                used[crossed] -= 1;
+            }
+            // The word has to play another copy of the letter, not just cross one
+            if (!fits || used[letter] === 0) {
+                continue;
+            }
+            used.forEach((n, i) => on_board[i] += n);
+            const res = search(remaining.map((n, i) => n - used[i]), false);
+            used.forEach((n, i) => on_board[i] -= n);
+            if (res !== false) {
+                return res;
+            }
+        }
+        return false;
+    };
+    return search(letters, true);
+}
+
+/**
+ * Async function to check a hand for well-known patterns that make it unsolvable (or nearly so), like a Q without a U
+ * @param available_letters Mapping of string letters to numeric quantity of each letter
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether to check against the full Scrabble dictionary rather than the common words
+ * @returns The patterns found, along with suggested letters to dump
+ */
+export async function detect_dead_end_patterns(available_letters: Map<string, number>, state: AppState, use_long_dictionary: boolean) {
+    return new Promise<DeadEndAnalysis>((resolve, reject) => {
+        const unavailable = dictionary_unavailable(use_long_dictionary);
+        if (unavailable != null) {
+            reject(unavailable);
+            return;
+        }
+        const letters = letters_from_map(available_letters);
+        if (typeof letters === "string") {
+            reject(letters);
+            return;
+        }
+        const makeable = get_dictionary(state, use_long_dictionary).filter(word => is_makeable(word, letters));
+        // Whether each letter appears in any makeable word
+        const in_word = new Array<boolean>(26).fill(false);
+        for (const word of makeable) {
+            word.forEach(letter => in_word[letter] = true);
+        }
+        const patterns: DeadEndPattern[] = [];
+        const suggested_dumps = new Set<string>();
+        for (let i=0; i<26; i++) {
+            if (letters[i] === 0) {
+                continue;
+            }
+            const c = UPPERCASE.charAt(i);
+            if (!in_word[i]) {
+                if (i === 16 && letters[20] === 0) {
+                    patterns.push({letters: "Q", description: "There is a Q without a U, and no word using the Q can be made from this hand"});
+                }
+                else {
+                    patterns.push({letters: c, description: "No word using " + c + " can be made from this hand"});
+                }
+                suggested_dumps.add(c);
+            }
+            // Several copies can go in separate words, so only warn if there's no way to play them all (and not if the search gave up)
+            else if (RARE_LETTERS.includes(i) && letters[i] > 1 && can_play_every_copy(i, letters, makeable.filter(word => word.includes(i))) === false) {
+                patterns.push({letters: c.repeat(letters[i]), description: "There are " + letters[i] + " " + c + "s, but no set of words that can be made from this hand uses all of them"});
+                suggested_dumps.add(c);
+            }
+        }
+        const total = letters.reduce((a, b) => a + b, 0);
+        // Y counts as a vowel here, since words like RHYTHM and SHY are built around it
+        const vowels_and_y = [...VOWELS, 24];
+        const num_vowels = vowels_and_y.reduce((a, i) => a + letters[i], 0);
+        if (total > 1 && (num_vowels === total || num_vowels === 0)) {
+            // Suggest dumping whichever letter there is the most of
+            let most = 0;
+            for (let i=1; i<26; i++) {
+                if (letters[i] > letters[most]) {
+                    most = i;
+                }
+            }
+            if (num_vowels === total) {
+                patterns.push({letters: convert_array_to_word(Uint8Array.from(vowels_and_y.filter(i => letters[i] > 0))), description: "The hand is made entirely of vowels (counting Y)"});
+            }
+            else {
+                patterns.push({letters: UPPERCASE.split("").filter((_, i) => letters[i] > 0).join(""), description: "The hand has no vowels, not even a Y"});
+            }
+            suggested_dumps.add(UPPERCASE.charAt(most));
+        }
+        resolve({patterns: patterns, suggested_dumps: [...suggested_dumps]});
+    });
+}
+
+/**
+ * The words playable from a hand of letters
+ */
+export interface PlayableWordsResult {
+    /**
+     * Common words playable using the hand (only those in the requested page)
+     */
+    short: string[],
+    /**
+     * Scrabble words playable using the hand (only those in the requested page)
+     */
+    long: string[],
+    /**
+     * Total number of playable common words, across all pages
+     */
+    total_short: number,
+    /**
+     * Total number of playable Scrabble words, across all pages
+     */
+    total_long: number,
+    /**
+     * How many tiles from the hand each word in `short` uses
+     */
+    short_consumed: number[],
+    /**
+     * How many tiles from the hand each word in `long` uses
+     */
+    long_consumed: number[],
+    /**
+     * Total number of tiles in the hand
+     */
+    hand_size: number,
+    /**
+     * How common each word in `long` is, from 1 (rare) to 5 (very common); only present if `AppState.all_words_long_commonness` is loaded
+     */
+    long_commonness?: number[],
+    /**
+     * Whether the full Scrabble dictionary is in this build (see `dictionary_capabilities`); if not, `long` is always empty
+     */
+    long_available: boolean
+}
+
+/**
+ * Maximum number of words kept in `AppState.word_history`
+ */
+const WORD_HISTORY_LIMIT = 200;
+
+/**
+ * Adds the words on a solved board to the session's word history, dropping the oldest words once there are more than `WORD_HISTORY_LIMIT`
+ * @param history The current history (see `AppState.word_history`); this is not modified
+ * @param solved The state of the game upon solving (i.e. `solution_t.state`)
+ * @returns The new history, with each word appearing once at its most recent position
+ */
+export function record_word_history(history: string[], solved: GameState) {
+    const words = extract_placed_words(board_from_array(solved.board), solved.min_col, solved.max_col, solved.min_row, solved.max_row).map(placed => placed.word);
+    const added = new Set(words);
+    const updated = history.filter(word => !added.has(word)).concat(Array.from(added));
+    return updated.slice(Math.max(0, updated.length - WORD_HISTORY_LIMIT));
+}
+
+/**
+ * Forgets the words played earlier in the session (see `AppState.word_history`)
+ * @param state Current state of the app; this is not modified
+ * @returns A copy of `state` with an empty word history
+ */
+export function clear_history(state: AppState): AppState {
+    return {...state, word_history: []};
+}
+
+/**
+ * How playable words can be ordered: in dictionary order, by the most tiles used from the hand, or by the most common first
+ */
+export type playable_sort_t = "dictionary"|"consumed"|"commonness";
+
+/**
+ * Buckets each word in a dictionary by how common it is
+ * @param words Array of words to rank (see `convert_word_to_array`)
+ * @param common_words Words known to be common; if `ordered`, these must be sorted from most to least common (e.g. a frequency list)
+ * @param ordered Whether `common_words` is in order of frequency
+ * @returns Array parallel to `words` of each word's commonness from 1 (not in `common_words`) to 5; if `ordered`, each quarter of `common_words` gets one bucket from 5 down to 2, otherwise every word in `common_words` gets 5
+ */
+export function rank_commonness(words: Array<Uint8Array>, common_words: Array<Uint8Array>, ordered: boolean) {
+    const ranks = new Map<number, number>();
+    common_words.forEach((word, i) => {
+        const hash = vec_hasher(word);
+        if (!ranks.has(hash)) {
+            ranks.set(hash, ordered ? 5 - Math.floor(4*i/common_words.length) : 5);
+        }
+    });
+    return Uint8Array.from(words.map(word => ranks.get(vec_hasher(word)) ?? 1));
+}
+
+/**
+ * Gets the playable words in a dictionary, in the requested order
+ * @param dictionary Array of words (see `convert_word_to_array`)
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @param sort Whether to keep dictionary order (`"dictionary"`), put the words using the most tiles first (`"consumed"`, breaking ties by which use the most of the hand's most-stocked letter(s)), or put the most common words first (`"commonness"`)
+ * @param commonness How common each word in `dictionary` is (see `rank_commonness`); if not given, every word is treated as equally common
+ * @returns Each playable word along with the number of tiles from the hand it uses, how many of those are of the most-stocked letter(s), and its commonness (0 if unknown)
+ */
+function playable_with_consumption(dictionary: Array<Uint8Array>, letters: Uint8Array, sort: playable_sort_t, commonness?: Uint8Array) {
+    const most_stocked = Math.max(...letters);
+    const playable: Array<[Uint8Array, number, number, number]> = [];
+    dictionary.forEach((word, i) => {
+        const consumption = hand_consumption(word, letters, most_stocked);
+        if (consumption != null) {
+            playable.push([word, consumption[0], consumption[1], commonness?.[i] ?? 0]);
+        }
+    });
+    // `sort` is stable, so ties stay in dictionary order and pages remain stable
+    if (sort === "consumed") {
+        playable.sort((a, b) => b[1] - a[1] || b[2] - a[2]);
+    }
+    else if (sort === "commonness") {
+        playable.sort((a, b) => b[3] - a[3]);
+    }
+    return playable;
+}
+
+/**
+ * Async function to get the playable words for a given hand of letters
+ * 
+ * The order of the words is deterministic for a given hand and `sort`, so pages are stable across calls.
+ * @param available_letters Mapping string letters to numeric quanity of each letter
+ * @param state Current state of the app
+ * @param offset Number of playable words to skip in each dictionary before the page starts
+ * @param limit Maximum number of words to return from each dictionary; if not given, every word after `offset` is returned
+ * @param sort Whether to return words in dictionary order (`"dictionary"`), with those using the most tiles from the hand first (`"consumed"`), or with the most common first (`"commonness"`; common words are all equally common)
+ * @returns Object with the "short" (common words playable using `available_letters`) and "long" (Scrabble words playable using `available_letters`) words in the page, along with the total count of each and how many tiles each word uses
+ */
+export async function get_playable_words(available_letters: Map<string, number>, state: AppState, offset = 0, limit = Infinity, sort: playable_sort_t = "dictionary") {
+    return new Promise<PlayableWordsResult>((resolve, reject) => {
+        // Check if we have all the letters from the frontend
+        const letters = letters_from_map(available_letters);
+        if (typeof letters === "string") {
+            reject(letters);
+            return;
+        }
+        if (offset < 0 || limit < 0) {
+            reject("The offset and limit must be greater than or equal to 0");
+            return;
+        }
+        resolve(playable_words_page(letters, state, offset, limit, sort));
+    });
+}
+
+/**
+ * Gets a page of the playable words for a hand (see `get_playable_words`)
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @param state Current state of the app
+ * @param offset Number of playable words to skip in each dictionary before the page starts
+ * @param limit Maximum number of words to return from each dictionary
+ * @param sort Order in which to return the words
+ * @returns The page of playable words
+ */
+function playable_words_page(letters: Uint8Array, state: AppState, offset: number, limit: number, sort: playable_sort_t): PlayableWordsResult {
+    const long_available = dictionary_capabilities().long;
+    // Only the words in the page are converted to strings
+    const playable_short = playable_with_consumption(state.all_words_short, letters, sort);
+    const playable_long = long_available ? playable_with_consumption(state.all_words_long, letters, sort, state.all_words_long_commonness) : [];
+    return {
+        short: playable_short.slice(offset, offset + limit).map(p => convert_array_to_word(p[0])),
+        long: playable_long.slice(offset, offset + limit).map(p => convert_array_to_word(p[0])),
+        total_short: playable_short.length,
+        total_long: playable_long.length,
+        short_consumed: playable_short.slice(offset, offset + limit).map(p => p[1]),
+        long_consumed: playable_long.slice(offset, offset + limit).map(p => p[1]),
+        hand_size: letters.reduce((a, b) => a + b, 0),
+        long_commonness: state.all_words_long_commonness == null ? undefined : playable_long.slice(offset, offset + limit).map(p => p[3]),
+        long_available: long_available
+    };
+}
+
+/**
+ * What can still be played after setting aside the letters of one word
+ */
+export interface PlayableAfterResult {
+    /**
+     * The words playable with the rest of the hand
+     */
+    playable: PlayableWordsResult,
+    /**
+     * Whether the rest of the hand could be used up on its own board within the trial budget; `false` may just mean the budget ran out
+     */
+    remainder_solvable: boolean
+}
+
+/**
+ * Async function to get what can be played after setting aside the letters of a word, e.g. when planning a move ahead
+ * @param available_letters Mapping string letters to numeric quanity of each letter
+ * @param reserved_word The word to set aside
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether `reserved_word` may come from the full Scrabble dictionary rather than just the common words
+ * @param max_words_to_check Maximum number of words to try when checking whether the rest of the hand can be solved
+ * @returns The playable words for the rest of the hand, and whether the rest of the hand is solvable
+ */
+export async function playable_after(available_letters: Map<string, number>, reserved_word: string, state: AppState, use_long_dictionary: boolean, max_words_to_check = 1000) {
+    return new Promise<PlayableAfterResult>((resolve, reject) => {
+        const unavailable = dictionary_unavailable(use_long_dictionary);
+        if (unavailable != null) {
+            reject(unavailable);
+            return;
+        }
+        const letters = letters_from_map(available_letters);
+        if (typeof letters === "string") {
+            reject(letters);
+            return;
+        }
+        const upper = reserved_word.toUpperCase();
+        if (!/^[A-Z]+$/.test(upper)) {
+            reject("Words may only contain the letters A-Z");
+            return;
+        }
+        const word = convert_word_to_array(upper);
+        if (!dictionary_set(get_dictionary(state, use_long_dictionary)).has(vec_hasher(word))) {
+            reject(upper + " is not in the dictionary");
+            return;
+        }
+        if (!is_makeable(word, letters)) {
+            reject(upper + " can't be made from the current letters");
+            return;
+        }
+        const remaining = Uint8Array.from(letters);
+        for (const letter of word) {
+            remaining[letter] -= 1;
+        }
+        let remainder_solvable = true;
+        if (remaining.some(count => count > 0)) {
+            const res = play_from_scratch(remaining, state, {max_words_to_check: max_words_to_check}, new Date());
+            remainder_solvable = typeof res !== "string" && "board" in res;
+        }
+        resolve({
+            playable: playable_words_page(remaining, state, 0, Infinity, "dictionary"),
+            remainder_solvable: remainder_solvable
+        });
+    });
+}
+
+/**
+ * Cache of the letter bitmask of each word in each dictionary (see `letter_mask`)
+ */
+const LETTER_MASKS = new WeakMap<Array<Uint8Array>, Uint32Array>();
+
+/**
+ * Gets the bitmask of which letters are present in a word or hand
+ * @param letters Either a word (see `convert_word_to_array`), or `null` to use `counts`
+ * @param counts Length-26 array of the number of each letter (only used if `letters` is `null`)
+ * @returns Bitmask with bit `i` set if letter `i` is present
+ */
+function letter_mask(letters: Uint8Array|null, counts?: Uint8Array) {
+    let mask = 0;
+    if (letters != null) {
+        for (const letter of letters) {
+            mask |= 1 << letter;
+        }
+    }
+    else if (counts != null) {
+        for (let i=0; i<26; i++) {
+            if (counts[i] > 0) {
+                mask |= 1 << i;
+            }
+        }
+    }
+    return mask >>> 0;
+}
+
+/**
+ * Gets the letter bitmask of every word in a dictionary, computing them the first time the dictionary is seen
+ * @param dictionary Array of words (see `convert_word_to_array`)
+ * @returns Array of the bitmask of each word in `dictionary`
+ */
+function dictionary_masks(dictionary: Array<Uint8Array>) {
+    let masks = LETTER_MASKS.get(dictionary);
+    if (masks == null) {
+        masks = Uint32Array.from(dictionary.map(word => letter_mask(word)));
+        LETTER_MASKS.set(dictionary, masks);
+    }
+    return masks;
+}
+
+/**
+ * Which words of a dictionary contain each letter
+ */
+interface LetterIndex {
+    /**
+     * For each letter, the indices into the dictionary of the words containing it, in increasing order
+     */
+    words: Uint32Array[],
+    /**
+     * For each letter, how many times each word in `words` contains it
+     */
+    counts: Uint8Array[]
+}
+
+/**
+ * Cache of the per-letter index of each dictionary (see `letter_index`); replaced to drop every index when memory runs low
+ */
+let LETTER_INDICES = new WeakMap<Array<Uint8Array>, LetterIndex>();
+
+/**
+ * Roughly how much memory the solver's optional large allocations are using
+ */
+export interface MemoryStats {
+    /**
+     * Bytes used by the cached per-letter word indices
+     */
+    index_bytes: number,
+    /**
+     * Bytes used by the opening boards collected by the current or last search (see `SolverConfig.collect_opening_boards`)
+     */
+    opening_board_bytes: number
+}
+
+/**
+ * The solver's current memory use (see `memory_stats`)
+ */
+const MEMORY_USAGE: MemoryStats = {index_bytes: 0, opening_board_bytes: 0};
+
+/**
+ * Gets roughly how much memory the solver's optional large allocations are using, e.g. to show on low-memory devices
+ * @returns Estimated bytes used by each kind of allocation
+ */
+export function memory_stats(): MemoryStats {
+    return {...MEMORY_USAGE};
+}
+
+/**
+ * Checks whether the solver may make another optional large allocation
+ * @param config Options for the solver
+ * @param bytes Estimated size of the allocation
+ * @returns Whether the allocation fits under `config.max_memory_bytes`
+ */
+function memory_available(config: SolverConfig, bytes: number) {
+    return config.max_memory_bytes == null || MEMORY_USAGE.index_bytes + MEMORY_USAGE.opening_board_bytes + bytes <= config.max_memory_bytes;
+}
+
+/**
+ * Drops the cached per-letter word indices if the solver is over its memory limit
+ * @param config Options for the solver
+ */
+function free_memory_if_needed(config: SolverConfig) {
+    if (!memory_available(config, 0)) {
+        LETTER_INDICES = new WeakMap();
+        MEMORY_USAGE.index_bytes = 0;
+    }
+}
+
+/**
+ * Gets which words of a dictionary contain each letter, building the index the first time the dictionary is seen
+ * @param dictionary Array of words (see `convert_word_to_array`)
+ * @returns The words containing each letter, and how many times they contain it
+ */
+export function letter_index(dictionary: Array<Uint8Array>) {
+    let index = LETTER_INDICES.get(dictionary);
+    if (index == null) {
+        const words: number[][] = Array.from({length: 26}, () => []);
+        const counts: number[][] = Array.from({length: 26}, () => []);
+        const word_counts = new Uint8Array(26);
+        dictionary.forEach((word, i) => {
+            word_counts.fill(0);
+            word.forEach(letter => word_counts[letter] += 1);
+            word_counts.forEach((count, letter) => {
+                if (count > 0) {
+                    words[letter].push(i);
+                    counts[letter].push(count);
                 }
-                else {
-                    row_vec.push(c + "*");
+            });
+        });
+        index = {words: words.map(w => Uint32Array.from(w)), counts: counts.map(c => Uint8Array.from(c))};
+        LETTER_INDICES.set(dictionary, index);
+        // Four bytes per index and one per count
+        MEMORY_USAGE.index_bytes += 5*words.reduce((a, w) => a + w.length, 0);
+    }
+    return index;
+}
+
+/**
+ * Cache of which letters appear anywhere in each list of words (see `coverage_mask`)
+ */
+const COVERAGE_MASKS = new WeakMap<Array<Uint8Array>, number>();
+
+/**
+ * Gets the bitmask of the letters that appear in at least one of a list of words, computing it the first time the list is seen
+ * @param words Array of words (see `convert_word_to_array`); since the result is cached, this must not be modified afterwards
+ * @returns Bitmask with bit `i` set if any word contains letter `i`
+ */
+function coverage_mask(words: Array<Uint8Array>) {
+    let mask = COVERAGE_MASKS.get(words);
+    if (mask == null) {
+        mask = 0;
+        for (const word of words) {
+            mask |= letter_mask(word);
+        }
+        mask >>>= 0;
+        COVERAGE_MASKS.set(words, mask);
+    }
+    return mask;
+}
+
+/**
+ * Cache of the fingerprint of each list of words (see `dictionary_fingerprint`)
+ */
+const DICTIONARY_FINGERPRINTS = new WeakMap<Array<Uint8Array>, string>();
+
+/**
+ * Gets a fingerprint of the contents of a list of words, computing it the first time the list is seen; unlike the list itself, this
+ * survives being posted to the worker, so it can tell whether two copies hold the same words
+ * @param words Array of words (see `convert_word_to_array`); since the result is cached, this must not be modified afterwards
+ * @returns A string that's the same for any two lists of the same words in the same order, and almost certainly different otherwise
+ */
+function dictionary_fingerprint(words: Array<Uint8Array>) {
+    let fingerprint = DICTIONARY_FINGERPRINTS.get(words);
+    if (fingerprint == null) {
+        let hash = 0x811c9dc5;
+        for (const word of words) {
+            hash = Math.imul(hash ^ vec_hasher(word), 0x01000193);
+        }
+        fingerprint = words.length + "-" + (hash >>> 0).toString(16);
+        DICTIONARY_FINGERPRINTS.set(words, fingerprint);
+    }
+    return fingerprint;
+}
+
+/**
+ * Cache of whether each list of words has any word made only of consonants, and any made only of vowels (see `vowel_balance_plausible`)
+ */
+const ONE_SIDED_WORDS = new WeakMap<Array<Uint8Array>, [boolean, boolean]>();
+
+/**
+ * Checks whether the letters left in the hand could plausibly be played: many letters that are all vowels or all consonants probably can't be
+ * unless some word is also all vowels or all consonants (e.g. "CRWTH")
+ * @param letters Length-26 array of the number of each letter left in the hand
+ * @param words Array of the words being tried (see `convert_word_to_array`); since the result is cached, this must not be modified afterwards
+ * @returns Whether the search should continue
+ */
+function vowel_balance_plausible(letters: Uint8Array, words: Array<Uint8Array>) {
+    const vowels = VOWELS.reduce((a, i) => a + letters[i], 0);
+    const total = letters.reduce((a, b) => a + b, 0);
+    if (total < VOWEL_PRUNE_MIN_TILES || (vowels > 0 && vowels < total)) {
+        return true;
+    }
+    let one_sided = ONE_SIDED_WORDS.get(words);
+    if (one_sided == null) {
+        const vowel_mask = letter_mask(Uint8Array.from(VOWELS));
+        one_sided = [words.some(word => (letter_mask(word) & vowel_mask) === 0), words.some(word => (letter_mask(word) & ~vowel_mask) === 0)];
+        ONE_SIDED_WORDS.set(words, one_sided);
+    }
+    return vowels === 0 ? one_sided[0] : one_sided[1];
+}
+
+/**
+ * The number of playable words for a hand
+ */
+export interface PlayableWordsCount {
+    /**
+     * Number of playable common words
+     */
+    short: number,
+    /**
+     * Number of playable Scrabble words
+     */
+    long: number,
+    /**
+     * Number of playable common words of each length (indexed by length)
+     */
+    short_by_length: number[],
+    /**
+     * Number of playable Scrabble words of each length (indexed by length)
+     */
+    long_by_length: number[]
+}
+
+/**
+ * Async function to count the playable words for a given hand, without building the words themselves; always agrees with `get_playable_words`
+ * @param available_letters Mapping of string letters to numeric quantity of each letter
+ * @param state Current state of the app
+ * @returns The number of playable words in each dictionary, overall and by length
+ */
+export async function count_playable_words(available_letters: Map<string, number>, state: AppState) {
+    return new Promise<PlayableWordsCount>((resolve, reject) => {
+        const letters = letters_from_map(available_letters);
+        if (typeof letters === "string") {
+            reject(letters);
+            return;
+        }
+        const hand_mask = letter_mask(null, letters);
+        const count = (dictionary: Array<Uint8Array>): [number, number[]] => {
+            const masks = dictionary_masks(dictionary);
+            const by_length: number[] = [];
+            let total = 0;
+            for (let i=0; i<dictionary.length; i++) {
+                // Words using any letter not in the hand can be skipped without counting letters
+                if ((masks[i] & ~hand_mask) === 0 && is_makeable(dictionary[i], letters)) {
+                    total += 1;
+                    by_length[dictionary[i].length] = (by_length[dictionary[i].length] ?? 0) + 1;
+                }
+            }
+            return [total, Array.from(by_length, n => n ?? 0)];
+        };
+        const [short, short_by_length] = count(state.all_words_short);
+        const [long, long_by_length] = count(state.all_words_long);
+        resolve({short: short, long: long, short_by_length: short_by_length, long_by_length: long_by_length});
+    });
+}
+
+/**
+ * Async function to find the words that can't be played now but could be with exactly one more tile, e.g. as a hint for what to hope to draw
+ * @param available_letters Mapping of string letters to numeric quantity of each letter
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether to use the full Scrabble dictionary rather than the common words
+ * @returns Mapping of each letter that would make some word playable to those words, in alphabetical order
+ */
+export async function words_one_tile_away(available_letters: Map<string, number>, state: AppState, use_long_dictionary: boolean) {
+    return new Promise<Map<string, string[]>>((resolve, reject) => {
+        const unavailable = dictionary_unavailable(use_long_dictionary);
+        if (unavailable != null) {
+            reject(unavailable);
+            return;
+        }
+        const letters = letters_from_map(available_letters);
+        if (typeof letters === "string") {
+            reject(letters);
+            return;
+        }
+        const hand_mask = letter_mask(null, letters);
+        const dictionary = get_dictionary(state, use_long_dictionary);
+        const masks = dictionary_masks(dictionary);
+        const by_letter: string[][] = Array.from({length: 26}, () => []);
+        const counts = new Uint8Array(26);
+        for (let i=0; i<dictionary.length; i++) {
+            const missing_mask = masks[i] & ~hand_mask;
+            // Words needing two or more letters not in the hand can be skipped without counting letters
+            if ((missing_mask & (missing_mask - 1)) !== 0) {
+                continue;
+            }
+            counts.fill(0);
+            dictionary[i].forEach(letter => counts[letter] += 1);
+            let needed = -1;
+            let short_by = 0;
+            for (let j=0; j<26 && short_by < 2; j++) {
+                if (counts[j] > letters[j]) {
+                    short_by += counts[j] - letters[j];
+                    needed = j;
+                }
+            }
+            if (short_by === 1) {
+                by_letter[needed].push(convert_array_to_word(dictionary[i]));
+            }
+        }
+        const result = new Map<string, string[]>();
+        by_letter.forEach((words, i) => {
+            if (words.length > 0) {
+                result.set(UPPERCASE[i], words);
+            }
+        });
+        resolve(result);
+    });
+}
+
+/**
+ * How important a single tile in the hand is
+ */
+export interface TileValue {
+    /**
+     * The letter of the tile
+     */
+    letter: string,
+    /**
+     * How many playable words would no longer be playable if one of this tile were removed from the hand
+     */
+    words_lost: number
+}
+
+/**
+ * Async function to rank the tiles in a hand by how many playable words depend on them, to help decide which to keep
+ * @param available_letters Mapping of string letters to numeric quantity of each letter
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether to use the full Scrabble dictionary rather than the common words
+ * @returns Each distinct letter in the hand, sorted from the most valuable (losing it loses the most words) to the least
+ */
+export async function most_valuable_tile(available_letters: Map<string, number>, state: AppState, use_long_dictionary: boolean) {
+    return new Promise<TileValue[]>((resolve, reject) => {
+        const unavailable = dictionary_unavailable(use_long_dictionary);
+        if (unavailable != null) {
+            reject(unavailable);
+            return;
+        }
+        const letters = letters_from_map(available_letters);
+        if (typeof letters === "string") {
+            reject(letters);
+            return;
+        }
+        // A playable word is lost by removing one of a letter exactly when it uses every copy of that letter in the hand
+        const words_lost = new Array<number>(26).fill(0);
+        for (const word of get_dictionary(state, use_long_dictionary)) {
+            if (is_makeable(word, letters)) {
+                const counts = new Uint8Array(26);
+                for (const letter of word) {
+                    counts[letter] += 1;
+                }
+                for (let i=0; i<26; i++) {
+                    if (counts[i] > 0 && counts[i] === letters[i]) {
+                        words_lost[i] += 1;
+                    }
+                }
+            }
+        }
+        const ranking: TileValue[] = [];
+        for (let i=0; i<26; i++) {
+            if (letters[i] > 0) {
+                ranking.push({letter: UPPERCASE.charAt(i), words_lost: words_lost[i]});
+            }
+        }
+        ranking.sort((a, b) => b.words_lost - a.words_lost);
+        resolve(ranking);
+    });
+}
+
+/**
+ * Cache of each dictionary's words bucketed by length, for matching fixed-length patterns
+ */
+const LENGTH_BUCKETS = new WeakMap<Array<Uint8Array>, Map<number, Array<Uint8Array>>>();
+
+/**
+ * Gets the words of the given length in a dictionary, bucketing the dictionary by length the first time it's seen
+ * @param dictionary Array of words (see `convert_word_to_array`)
+ * @param length Length of the words to get
+ * @returns Every word in `dictionary` of length `length`, in dictionary order
+ */
+function words_of_length(dictionary: Array<Uint8Array>, length: number) {
+    let buckets = LENGTH_BUCKETS.get(dictionary);
+    if (buckets == null) {
+        buckets = new Map();
+        for (const word of dictionary) {
+            const bucket = buckets.get(word.length);
+            if (bucket == null) {
+                buckets.set(word.length, [word]);
+            }
+            else {
+                bucket.push(word);
+            }
+        }
+        LENGTH_BUCKETS.set(dictionary, buckets);
+    }
+    return buckets.get(length) ?? [];
+}
+
+/**
+ * How long it took to build the cached indices of a dictionary (see `init_dictionaries`)
+ */
+export interface DictionaryWarmup {
+    /**
+     * Number of words in the dictionary
+     */
+    words: number,
+    /**
+     * How long building the indices took in milliseconds
+     */
+    elapsed: number
+}
+
+/**
+ * How long it took to build the cached indices of each dictionary
+ */
+export interface WarmupTimings {
+    /**
+     * The common words
+     */
+    short: DictionaryWarmup,
+    /**
+     * The full Scrabble dictionary; only present if it was warmed up
+     */
+    long?: DictionaryWarmup
+}
+
+/**
+ * Async function to build the cached indices of the dictionaries up front (e.g. right after they're loaded), rather than the first time each is needed,
+ * so that the first call to functions like `count_playable_words` or `search_words` isn't slower than later ones
+ *
+ * This only warms up the calling thread; each solve runs in its own worker, which builds the indices it needs itself.
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether to also warm up the full Scrabble dictionary; ignored if it isn't in this build
+ * @returns How long each dictionary took and how many words it has
+ */
+export async function init_dictionaries(state: AppState, use_long_dictionary: boolean) {
+    return new Promise<WarmupTimings>(resolve => {
+        /**
+         * Builds every cached index of a dictionary
+         * @param dictionary Array of words (see `convert_word_to_array`)
+         * @param with_set Whether to build the dictionary's hashed set too, which the common words already have in `AppState.all_words_short_set`
+         * @returns How long it took and how many words there are
+         */
+        const warm = (dictionary: Array<Uint8Array>, with_set: boolean): DictionaryWarmup => {
+            const start = performance.now();
+            dictionary_masks(dictionary);
+            words_of_length(dictionary, 0);
+            if (with_set) {
+                dictionary_set(dictionary);
+            }
+            return {words: dictionary.length, elapsed: performance.now() - start};
+        };
+        const timings: WarmupTimings = {short: warm(state.all_words_short, false)};
+        if (use_long_dictionary && dictionary_capabilities().long) {
+            timings.long = warm(state.all_words_long, true);
+        }
+        resolve(timings);
+    });
+}
+
+/**
+ * Async function to find the dictionary words matching a pattern, like "B_N_N__"
+ * @param pattern The pattern to match; letters must match exactly, `_` matches exactly one letter, and `*` matches zero or more letters
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether to search the full Scrabble dictionary rather than the common words
+ * @param limit Maximum number of words to return
+ * @param available_letters If given, only words that can also be made from this hand are returned
+ * @returns The matching words, in dictionary order
+ */
+export async function search_words(pattern: string, state: AppState, use_long_dictionary: boolean, limit: number, available_letters?: Map<string, number>) {
+    return new Promise<string[]>((resolve, reject) => {
+        const unavailable = dictionary_unavailable(use_long_dictionary);
+        if (unavailable != null) {
+            reject(unavailable);
+            return;
+        }
+        const upper = pattern.toUpperCase();
+        for (const c of upper) {
+            if (c !== "_" && c !== "*" && !UPPERCASE.includes(c)) {
+                reject("Invalid character in pattern: " + c);
+                return;
+            }
+        }
+        let letters: Uint8Array|null = null;
+        if (available_letters != null) {
+            const res = letters_from_map(available_letters);
+            if (typeof res === "string") {
+                reject(res);
+                return;
+            }
+            letters = res;
+        }
+        const matches: string[] = [];
+        if (!upper.includes("*")) {
+            // Fixed-length patterns only need to check words of the same length
+            const fixed = [...upper].map(c => c === "_" ? EMPTY_VALUE : c.charCodeAt(0) - 65);
+            for (const word of words_of_length(get_dictionary(state, use_long_dictionary), fixed.length)) {
+                if (matches.length >= limit) {
+                    break;
+                }
+                if (fixed.every((val, i) => val === EMPTY_VALUE || val === word[i]) && (letters == null || is_makeable(word, letters))) {
+                    matches.push(convert_array_to_word(word));
+                }
+            }
+        }
+        else {
+            const regex = new RegExp("^" + upper.replace(/_/g, ".").replace(/\*/g, ".*") + "$");
+            for (const word of get_dictionary(state, use_long_dictionary)) {
+                if (matches.length >= limit) {
+                    break;
+                }
+                const as_string = convert_array_to_word(word);
+                if (regex.test(as_string) && (letters == null || is_makeable(word, letters))) {
+                    matches.push(as_string);
+                }
+            }
+        }
+        resolve(matches);
+    });
+}
+
+/**
+ * A dictionary word containing a fragment
+ */
+export interface FragmentMatch {
+    /**
+     * The word containing the fragment
+     */
+    word: string,
+    /**
+     * Index in `word` at which the fragment starts
+     */
+    offset: number
+}
+
+/**
+ * Async function to find the dictionary words containing a fragment (e.g. for building off of a word on the board)
+ * @param fragment The contiguous letters that must be in each word
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether to search the full Scrabble dictionary rather than the common words
+ * @param limit Maximum number of matches to return
+ * @param available_letters If given, only matches whose letters outside of the fragment can be made from this hand are returned
+ * @returns Each word containing the fragment along with where the fragment is, in dictionary order; a word containing the fragment more than once is returned once per position
+ */
+export async function words_containing(fragment: string, state: AppState, use_long_dictionary: boolean, limit: number, available_letters?: Map<string, number>) {
+    return new Promise<FragmentMatch[]>((resolve, reject) => {
+        const unavailable = dictionary_unavailable(use_long_dictionary);
+        if (unavailable != null) {
+            reject(unavailable);
+            return;
+        }
+        const upper = fragment.toUpperCase();
+        if (upper.length === 0 || [...upper].some(c => !UPPERCASE.includes(c))) {
+            reject("The fragment must be made of one or more letters");
+            return;
+        }
+        let letters: Uint8Array|null = null;
+        if (available_letters != null) {
+            const res = letters_from_map(available_letters);
+            if (typeof res === "string") {
+                reject(res);
+                return;
+            }
+            letters = res;
+        }
+        const matches: FragmentMatch[] = [];
+        for (const word of get_dictionary(state, use_long_dictionary)) {
+            if (word.length < upper.length) {
+                continue;
+            }
+            const as_string = convert_array_to_word(word);
+            let offset = as_string.indexOf(upper);
+            while (offset !== -1 && matches.length < limit) {
+                if (letters == null || is_makeable(Uint8Array.from([...word.subarray(0, offset), ...word.subarray(offset + upper.length)]), letters)) {
+                    matches.push({word: as_string, offset: offset});
+                }
+                offset = as_string.indexOf(upper, offset + 1);
+            }
+            if (matches.length >= limit) {
+                break;
+            }
+        }
+        resolve(matches);
+    });
+}
+
+/**
+ * Async function to stream the playable words for a given hand of letters in chunks, so that they can be displayed before the whole dictionary has been checked
+ * 
+ * Control is yielded back to the event loop after each chunk. Words are delivered in dictionary order.
+ * @param available_letters Mapping string letters to numeric quanity of each letter
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether to use the full Scrabble dictionary rather than the common words
+ * @param on_chunk Called with each chunk of playable words
+ * @param chunk_size How many words to deliver in each chunk
+ * @returns The total number of playable words
+ */
+export async function stream_playable_words(available_letters: Map<string, number>, state: AppState, use_long_dictionary: boolean, on_chunk: (words: string[]) => void, chunk_size = 500) {
+    const letters = letters_from_map(available_letters);
+    if (typeof letters === "string") {
+        throw letters;
+    }
+    if (chunk_size < 1) {
+        throw "The chunk size must be greater than 0";
+    }
+    const unavailable = dictionary_unavailable(use_long_dictionary);
+    if (unavailable != null) {
+        throw unavailable;
+    }
+    let total = 0;
+    let chunk: string[] = [];
+    for (const word of get_dictionary(state, use_long_dictionary)) {
+        if (is_makeable(word, letters)) {
+            chunk.push(convert_array_to_word(word));
+            total += 1;
+            if (chunk.length === chunk_size) {
+                on_chunk(chunk);
+                chunk = [];
+                await new Promise(resolve => setTimeout(resolve, 0));
+            }
+        }
+    }
+    if (chunk.length > 0) {
+        on_chunk(chunk);
+    }
+    return total;
+}
+
+/**
+ * A hand that is known to be solvable
+ */
+export interface GeneratedHand {
+    /**
+     * Mapping of each uppercase Latin character to the number of times it's present
+     */
+    letters: Map<string, number>,
+    /**
+     * A solution of the hand
+     */
+    solution: solution_t,
+    /**
+     * How many hands were dealt before finding this one
+     */
+    attempts: number
+}
+
+/**
+ * Deals a hand from a standard Bananagrams bag that the solver can solve, e.g. for making puzzles; this is slow, so it's run in a worker
+ * (see `generate_solvable_hand` in `solver_tasks.ts`)
+ * @param size How many tiles to deal; must be between 1 and 144
+ * @param seed Seed for dealing, so that the same seed always gives the same hand
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether to solve with the full Scrabble dictionary rather than just the common words
+ * @param max_words_to_check Maximum number of words to try when solving each dealt hand
+ * @param max_attempts Maximum number of hands to deal before giving up
+ * @returns The first solvable hand dealt, along with a solution, or an error message if none was found
+ */
+function find_solvable_hand(size: number, seed: number, state: AppState, use_long_dictionary: boolean, max_words_to_check: number, max_attempts = 100): GeneratedHand|string {
+    if (size < 1 || size > 144) {
+        return "The number of tiles must be between 1 and 144";
+    }
+    const unavailable = dictionary_unavailable(use_long_dictionary);
+    if (unavailable != null) {
+        return unavailable;
+    }
+    const dictionary = get_dictionary(state, use_long_dictionary);
+    const solve_state: AppState = {...state, all_words_short: dictionary, all_words_short_set: use_long_dictionary ? dictionary_set(dictionary) : state.all_words_short_set};
+    const random = seededRandom(seed);
+    for (let attempt=1; attempt<=max_attempts; attempt++) {
+        // Deal from a full bag each time
+        const to_choose_from: number[] = [];
+        for (let i=0; i<26; i++) {
+            for (let j=0; j<REGULAR_TILES[i]; j++) {
+                to_choose_from.push(i);
+            }
+        }
+        const letters = new Uint8Array(26);
+        for (let i=0; i<size; i++) {
+            const random_num = getRandomInt(0, to_choose_from.length-1, random);
+            letters[to_choose_from[random_num]] += 1;
+            to_choose_from.splice(random_num, 1);
+        }
+        const res = play_from_scratch(letters, solve_state, {max_words_to_check: max_words_to_check}, new Date());
+        if (typeof res !== "string" && "board" in res) {
+            const as_map = new Map<string, number>();
+            [...UPPERCASE].forEach((c, i) => as_map.set(c, letters[i]));
+            return {letters: as_map, solution: res, attempts: attempt};
+        }
+    }
+    return "No solvable hand was found after dealing " + max_attempts + " hands";
+}
+
+/**
+ * Most tiles `minimal_addition` will try adding, since the number of hands to try grows very quickly with each tile
+ */
+const MAX_ADDITION = 3;
+
+/**
+ * The fewest tiles that make a hand solvable
+ */
+export interface MinimalAddition {
+    /**
+     * Mapping of each uppercase Latin character to the number of that letter to add (empty if the hand is already solvable)
+     */
+    added: Map<string, number>,
+    /**
+     * A solution of the hand with the tiles added
+     */
+    solution: solution_t,
+    /**
+     * How many hands were tried, including the original
+     */
+    hands_tried: number
+}
+
+/**
+ * Yields every multiset of a number of letters, with letters earlier in `order` first
+ * @param order Letters to choose from (from 0 for 'A' to 25 for 'Z')
+ * @param count How many letters to choose
+ * @param from Index into `order` of the first letter that may be chosen
+ * @yields Each multiset as an array of letters in `order` order
+ */
+function* letter_multisets(order: number[], count: number, from = 0): Generator<number[]> {
+    if (count === 0) {
+        yield [];
+        return;
+    }
+    for (let i=from; i<order.length; i++) {
+        for (const rest of letter_multisets(order, count-1, i)) {
+            yield [order[i], ...rest];
+        }
+    }
+}
+
+/**
+ * Async function to find the fewest tiles that would make a hand solvable, e.g. to know what to hope for from the bag; the opposite of dumping
+ * @param available_letters Mapping string letters to numeric quanity of each letter
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether to solve with the full Scrabble dictionary rather than just the common words
+ * @param max_add Most tiles to try adding; must be between 1 and `MAX_ADDITION`
+ * @param max_words_to_check Maximum number of words to try when solving each hand
+ * @returns The fewest tiles to add, trying letters more common in the bag first, along with a solution
+ */
+export async function minimal_addition(available_letters: Map<string, number>, state: AppState, use_long_dictionary: boolean, max_add: number, max_words_to_check: number) {
+    return new Promise<MinimalAddition>((resolve, reject) => {
+        const unavailable = dictionary_unavailable(use_long_dictionary);
+        if (unavailable != null) {
+            reject(unavailable);
+            return;
+        }
+        const letters = letters_from_map(available_letters);
+        if (typeof letters === "string") {
+            reject(letters);
+            return;
+        }
+        if (max_add < 1 || max_add > MAX_ADDITION) {
+            reject("The number of tiles to add must be between 1 and " + MAX_ADDITION);
+            return;
+        }
+        const dictionary = get_dictionary(state, use_long_dictionary);
+        const solve_state: AppState = {...state, all_words_short: dictionary, all_words_short_set: use_long_dictionary ? dictionary_set(dictionary) : state.all_words_short_set};
+        // Stable, so letters as common as each other stay in alphabetical order
+        const order = Array.from({length: 26}, (_, i) => i).sort((a, b) => REGULAR_TILES[b] - REGULAR_TILES[a]);
+        let hands_tried = 0;
+        for (let count=0; count<=max_add; count++) {
+            for (const added of letter_multisets(order, count)) {
+                const hand = Uint8Array.from(letters);
+                added.forEach(letter => hand[letter] += 1);
+                hands_tried += 1;
+                const res = play_from_scratch(hand, solve_state, {max_words_to_check: max_words_to_check}, new Date());
+                if (typeof res !== "string" && "board" in res) {
+                    const as_map = new Map<string, number>();
+                    added.forEach(letter => as_map.set(UPPERCASE[letter], (as_map.get(UPPERCASE[letter]) ?? 0) + 1));
+                    resolve({added: as_map, solution: res, hands_tried: hands_tried});
+                    return;
                 }
             }
         }
-        board_vec.push(row_vec);
-    }
-    return board_vec;
+        reject("No addition of up to " + max_add + " tiles was found that makes the hand solvable within the budget");
+    });
 }
 
 /**
- * Checks whether a `word` can be made using the given `letters`
- * @param word The array form of the word to check
+ * The smallest part of a hand that can be solved on its own
+ */
+export interface SubsetResult {
+    /**
+     * Mapping of each uppercase Latin character to the number of that letter in the solved part of the hand
+     */
+    subset: Map<string, number>,
+    /**
+     * A solution of `subset`
+     */
+    solution: solution_t,
+    /**
+     * Mapping of each uppercase Latin character to the number of that letter in the rest of the hand
+     */
+    leftover: Map<string, number>,
+    /**
+     * How many parts of the hand were tried
+     */
+    hands_tried: number
+}
+
+/**
+ * Yields every distinct part of a hand with a given number of tiles
  * @param letters Length-26 array of the number of each letter in the hand
- * @returns Whether `word` can be made using `letters
- *
+ * @param count How many tiles each part should have
+ * @param from First letter (from 0 for 'A' to 25 for 'Z') that may be chosen
+ * @param part The part being built (is modified in-place)
+ * @yields Each part as a length-26 array of the number of each letter; the same array is reused, so it must be copied to be kept
  */
-function is_makeable(word: Uint8Array, letters: Uint8Array) {
-    const available_letters = new Uint8Array(letters.length);
-    for (let i=0; i<letters.length; i++) {
-        available_letters[i] = letters[i];
+function* sub_hands(letters: Uint8Array, count: number, from = 0, part = new Uint8Array(26)): Generator<Uint8Array> {
+    if (count === 0) {
+        yield part;
+        return;
     }
-    for (let i=0; i<word.length; i++) {
-        if (available_letters[word[i]] === 0) {
-            return false;
+    for (let i=from; i<26; i++) {
+        if (part[i] < letters[i]) {
+            part[i] += 1;
+            yield* sub_hands(letters, count-1, i, part);
+            part[i] -= 1;
         }
-        available_letters[word[i]] -= 1;
     }
-    return true;
 }
 
 /**
- * Checks which words can be played after the first
- * @param letters Length-26 array of originally available letters
- * @param word_being_checked Word that is being checked if playable
- * @param played_on_board Set of the letters played on the board
- * @returns Whether the `word_being_checked` is playable
+ * Finds the fewest tiles of a hand that can be solved on their own, e.g. to know how much of the hand is needed to play right now;
+ * this is slow, so it's run in a worker (see `smallest_solvable_subset` in `solver_tasks.ts`)
+ *
+ * The number of tiles is doubled from two until some part of the hand can be solved, and then binary searched below that, which assumes that if
+ * some part of the hand can be solved, then so can some larger part; every distinct part of each size tried is solved until one succeeds.
+ * The whole hand is only solved if no smaller part can be.
+ * @param available_letters Mapping string letters to numeric quanity of each letter
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether to solve with the full Scrabble dictionary rather than just the common words
+ * @param max_words_to_check Maximum number of words to try when solving each part of the hand
+ * @returns The smallest solvable part of the hand found, along with a solution and the rest of the hand, or an error message if no part could be solved
  */
-function check_filter_after_play(letters: Uint8Array, word_being_checked: Uint8Array, played_on_board: Set<number>) {
-    const available_letters = new Int8Array(letters);
-    let already_seen_negative = false;
-    for (const letter of word_being_checked) {
-        const num_left = available_letters[letter];
-        if (num_left === 0 && !played_on_board.has(letter)) {
-            return false;
+function find_smallest_solvable_subset(available_letters: Map<string, number>, state: AppState, use_long_dictionary: boolean, max_words_to_check: number): SubsetResult|string {
+    const unavailable = dictionary_unavailable(use_long_dictionary);
+    if (unavailable != null) {
+        return unavailable;
+    }
+    const letters = letters_from_map(available_letters);
+    if (typeof letters === "string") {
+        return letters;
+    }
+    const dictionary = get_dictionary(state, use_long_dictionary);
+    const solve_state: AppState = {...state, all_words_short: dictionary, all_words_short_set: use_long_dictionary ? dictionary_set(dictionary) : state.all_words_short_set};
+    let hands_tried = 0;
+    /**
+     * Solves each part of the hand with `count` tiles until one succeeds
+     * @param count How many tiles to use
+     * @returns The part solved and its solution, or `null` if no part could be solved within the budget
+     */
+    const solve_size = (count: number): [Uint8Array, solution_t]|null => {
+        for (const part of sub_hands(letters, count)) {
+            hands_tried += 1;
+            const hand = Uint8Array.from(part);
+            const res = play_from_scratch(hand, solve_state, {max_words_to_check: max_words_to_check}, new Date());
+            if (typeof res !== "string" && "board" in res) {
+                return [hand, res];
+            }
         }
-        else if (num_left === 0 && already_seen_negative) {
-            return false;
+        return null;
+    };
+    const total = letters.reduce((a, b) => a + b, 0);
+    // The smallest size that can be solved is above `low` and at most `high`
+    let low = 1;
+    let high = Math.min(2, total);
+    let best = solve_size(high);
+    while (best == null && high < total) {
+        low = high;
+        high = Math.min(2*high, total);
+        best = solve_size(high);
+    }
+    if (best == null) {
+        return "No part of the hand could be solved within the budget";
+    }
+    while (high - low > 1) {
+        const mid = Math.floor((low + high)/2);
+        const found = solve_size(mid);
+        if (found == null) {
+            low = mid;
         }
-        else if (num_left === 0) {
-            already_seen_negative = true;
+        else {
+            best = found;
+            high = mid;
         }
-        available_letters[letter] -= 1;
     }
-    return true;
+    const [subset, solution] = best;
+    const subset_map = new Map<string, number>();
+    const leftover_map = new Map<string, number>();
+    [...UPPERCASE].forEach((c, i) => {
+        subset_map.set(c, subset[i]);
+        leftover_map.set(c, letters[i] - subset[i]);
+    });
+    return {subset: subset_map, solution: solution, leftover: leftover_map, hands_tried: hands_tried};
 }
 
 /**
- * Checks that a `board` is valid after a word is played horizontally, given the specified list of `valid_word`s
- * Note that this does not check if all words are contiguous; this condition must be enforced elsewhere.
- * @param board `Board` being checked
- * @param min_col Minimum x (column) index of the subsection of the `board` to be checked
- * @param max_col Maximum x (column) index of the subsection of the `board` to be checked
- * @param min_row Minimum y (row) index of the subsection of the `board` to be checked
- * @param max_row Maximum y (row) index of the subsection of the `board` to be checked
- * @param row Row of the word played
- * @param start_col Starting column of the word played
- * @param end_col Ending column of the word played
- * @param valid_words Set of all valid words
- * @returns Whether the given `board` is made only of valid words
+ * Async function to finish a board that already has some words on it, e.g. one the user started by hand, by playing the rest of the hand on top of it
+ *
+ * The tiles already on the board are never moved. The solution's play sequence only has the words played on top of the partial board, so it can't be built off of by the next solve.
+ * @param partial_board The board array (e.g. `GameState.board`) with the words already placed
+ * @param min_col Minimum occupied column index of `partial_board`
+ * @param max_col Maximum occupied column index of `partial_board`
+ * @param min_row Minimum occupied row index of `partial_board`
+ * @param max_row Maximum occupied row index of `partial_board`
+ * @param remaining_letters Mapping string letters to numeric quanity of each letter still to be played
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether to solve with the full Scrabble dictionary rather than just the common words
+ * @param filter_letters_on_board How many letters already on the board a word may play through; words needing more than this many letters that aren't in `remaining_letters` aren't tried
+ * @param max_words_to_check Maximum number of words to try
+ * @returns The finished board, with the tiles of `partial_board` marked as previously played
  */
-function is_board_valid_horizontal(board: Board, min_col: number, max_col: number, min_row: number, max_row: number, row: number, start_col: number, end_col: number, valid_words: Set<number>) {
-    let current_letters: number[] = [];
-    // Check across the row where the word was played
-    for (let col_idx=min_col; col_idx<max_col+1; col_idx++) {
-        // If we're not at an empty square, add it to the current word we're looking at
-        if (board.get_val(row, col_idx) != EMPTY_VALUE) {
-            current_letters.push(board.get_val(row, col_idx));
+export async function play_from_partial(partial_board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number, remaining_letters: Map<string, number>, state: AppState, use_long_dictionary: boolean, filter_letters_on_board: number, max_words_to_check: number) {
+    return new Promise<solution_t>((resolve, reject) => {
+        const unavailable = dictionary_unavailable(use_long_dictionary);
+        if (unavailable != null) {
+            reject(unavailable);
+            return;
         }
-        else {
-            if (current_letters.length > 1 && !valid_words.has(vec_hasher(current_letters))) {
-                return false;
-            }
-            current_letters = [];
-            if (col_idx > end_col) {
-                break;
-            }
+        const start = new Date();
+        const letters = letters_from_map(remaining_letters);
+        if (typeof letters === "string") {
+            reject(letters);
+            return;
         }
-    }
-    if (current_letters.length > 1 && !valid_words.has(vec_hasher(current_letters))) {
-        return false;
-    }
-    // Check down each column where a letter was played
-    for (let col_idx=start_col; col_idx<end_col+1; col_idx++) {
-        current_letters = [];
-        for (let row_idx=min_row; row_idx<max_row+1; row_idx++) {
-            if (board.get_val(row_idx, col_idx) != EMPTY_VALUE) {
-                current_letters.push(board.get_val(row_idx, col_idx));
+        if (min_col < 0 || min_row < 0 || max_col >= BOARD_SIZE || max_row >= BOARD_SIZE) {
+            reject("The board's bounds are outside of the board");
+            return;
+        }
+        // Copied so that the caller's board isn't changed
+        const board = board_from_array(Uint8Array.from(partial_board));
+        const dictionary = get_dictionary(state, use_long_dictionary);
+        const valid_words_set = use_long_dictionary ? dictionary_set(dictionary) : state.all_words_short_set;
+        if (!is_connected(board, min_col, max_col, min_row, max_row) || extract_placed_words(board, min_col, max_col, min_row, max_row).some(placed => !valid_words_set.has(vec_hasher(convert_word_to_array(placed.word))))) {
+            reject("The partial board isn't valid");
+            return;
+        }
+        const on_board = count_board_letters(board.arr, min_col, max_col, min_row, max_row);
+        const hand = Uint8Array.from(letters, (count, i) => count + on_board[i]);
+        const previous_idxs = new Set<number>();
+        for (let row=min_row; row<max_row+1; row++) {
+            for (let col=min_col; col<max_col+1; col++) {
+                if (board.get_val(row, col) != EMPTY_VALUE) {
+                    previous_idxs.add(vec_hasher([row, col]));
+                }
             }
-            else {
-                if (current_letters.length > 1 && !valid_words.has(vec_hasher(current_letters))) {
+        }
+        if (letters.every(count => count === 0)) {
+            resolve(build_solution(board, min_col, max_col, min_row, max_row, hand, [], previous_idxs, start, 0));
+            return;
+        }
+        // Each word may use letters from the hand, plus a few already on the board to play through
+        const valid_words_vec = dictionary.filter(word => {
+            const counts = new Uint8Array(26);
+            word.forEach(letter => counts[letter] += 1);
+            let borrowed = 0;
+            for (let i=0; i<26; i++) {
+                if (counts[i] > hand[i]) {
                     return false;
                 }
-                current_letters = [];
-                if (row_idx > row) {
-                    break;
-                }
+                borrowed += Math.max(0, counts[i] - letters[i]);
             }
+            return borrowed <= filter_letters_on_board;
+        });
+        const ctx = new_search_context(letters, {max_words_to_check: max_words_to_check});
+        const play_sequence: PlaySequence = [];
+        const res = play_further(board, min_col, max_col, min_row, max_row, valid_words_vec, valid_words_set, letters, 0, play_sequence, [], ctx);
+        if (res != null && res[0]) {
+            resolve(build_solution(board, res[1], res[2], res[3], res[4], hand, play_sequence, previous_idxs, start, ctx.words_checked));
         }
-        if (current_letters.length > 1 && !valid_words.has(vec_hasher(current_letters))) {
-            return false;
+        else if (ctx.exhausted) {
+            reject("No way to finish the board was found after checking " + ctx.words_checked + " words (the best partial board left " + ctx.fewest_remaining + " letters)");
         }
-    }
-    return true;
+        else {
+            reject("There's no way to play the remaining letters on the partial board");
+        }
+    });
 }
 
 /**
- * Checks that a `board` is valid after a word is played vertically, given the specified list of `valid_word`s
- * Note that this does not check if all words are contiguous; this condition must be enforced elsewhere.
- * @param board `Board` being checked
- * @param min_col Minimum x (column) index of the subsection of the `board` to be checked
- * @param max_col Maximum x (column) index of the subsection of the `board` to be checked
- * @param min_row Minimum y (row) index of the subsection of the `board` to be checked
- * @param max_row Maximum y (row) index of the subsection of the `board` to be checked
- * @param start_row Starting row of the word played
- * @param end_row Ending row of the word played
- * @param col Column of the word played
- * @param valid_words Set of all valid words
- * @returns Whether the given `board` is made only of valid words
+ * Counts the letters on a board
+ * @param board The board array (e.g. `GameState.board`)
+ * @param min_col Minimum occupied column index
+ * @param max_col Maximum occupied column index
+ * @param min_row Minimum occupied row index
+ * @param max_row Maximum occupied row index
+ * @returns Length-26 array of the number of each letter on the board
  */
-function is_board_valid_vertical(board: Board, min_col: number, max_col: number, min_row: number, max_row: number, start_row: number, end_row: number, col: number, valid_words: Set<number>) {
-    let current_letters: number[] = [];
-    // Check down the column where the word was played
-    for (let row_idx=min_row; row_idx<max_row+1; row_idx++) {
-        // If it's not an empty value, add it to the current word
-        if (board.get_val(row_idx, col) != EMPTY_VALUE) {
-            current_letters.push(board.get_val(row_idx, col));
-        }
-        else {
-            // Otherwise, check if we have more than one letter - if so, check if the word is valid
-            if (current_letters.length > 1 && !valid_words.has(vec_hasher(current_letters))) {
-                return false;
-            }
-            current_letters = [];
-            // If we're past the end of the played word, no need to check farther
-            if (row_idx > end_row) {
-                break;
+function count_board_letters(board: Uint8Array, min_col: number, max_col: number, min_row: number, max_row: number) {
+    const counts = new Uint8Array(26);
+    for (let row=min_row; row<max_row+1; row++) {
+        for (let col=min_col; col<max_col+1; col++) {
+            const val = board[row*BOARD_SIZE + col];
+            if (val != EMPTY_VALUE) {
+                counts[val] += 1;
             }
         }
     }
-    // In case we don't hit the `else` in the previous loop
-    if (current_letters.length > 1) {
-        if (!valid_words.has(vec_hasher(current_letters))) {
-            return false;
-        }
-    }
-    // Check across each row where a letter was played
-    for (let row_idx=start_row; row_idx<end_row+1; row_idx++) {
-        current_letters = [];
-        for (let col_idx=min_col; col_idx<max_col+1; col_idx++) {
-            if (board.get_val(row_idx, col_idx) != EMPTY_VALUE) {
-                current_letters.push(board.get_val(row_idx, col_idx));
-            }
-            else {
-                if (current_letters.length > 1 && !valid_words.has(vec_hasher(current_letters))) {
-                    return false;
-                }
-                current_letters = [];
-                if (col_idx > col) {
-                    break;
-                }
+    return counts;
+}
+
+/**
+ * Async function to work out what's left in the bag, given every player's hand and board
+ * @param hands Mapping of string letters to numeric quantity of each letter for each hand
+ * @param boards Each player's board (only the board and its bounds are used)
+ * @param what Whether the game is "standard Bananagrams" (144 tiles) or "double Bananagrams" (288 tiles)
+ * @returns Mapping of each uppercase Latin character to the number left in the bag
+ */
+export async function remaining_bag(hands: Array<Map<string, number>>, boards: Array<Pick<GameState, "board"|"min_col"|"max_col"|"min_row"|"max_row">>, what: "standard Bananagrams"|"double Bananagrams" = "standard Bananagrams") {
+    return new Promise<Map<string, number>>((resolve, reject) => {
+        const bag = REGULAR_TILES.map(count => what === "double Bananagrams" ? 2*count : count);
+        for (const hand of hands) {
+            const letters = letters_from_map(hand);
+            if (typeof letters === "string") {
+                reject(letters);
+                return;
             }
+            letters.forEach((count, i) => bag[i] -= count);
         }
-        if (current_letters.length > 1 && !valid_words.has(vec_hasher(current_letters))) {
-            return false;
+        for (const board of boards) {
+            count_board_letters(board.board, board.min_col, board.max_col, board.min_row, board.max_row).forEach((count, i) => bag[i] -= count);
         }
-    }
-    return true;
+        const over = bag.findIndex(count => count < 0);
+        if (over !== -1) {
+            reject("There are " + (-bag[over]) + " more " + UPPERCASE.charAt(over) + "'s in the hands and boards than in " + what);
+            return;
+        }
+        const return_chars = new Map<string, number>();
+        [...UPPERCASE].forEach((c, i) => return_chars.set(c, bag[i]));
+        resolve(return_chars);
+    });
 }
 
 /**
- * Plays a word on the board
- * @param word The word to be played
- * @param row_idx The starting row at which to play the word
- * @param col_idx The starting column at which to play the word
- * @param board The current board (is modified in-place)
- * @param direction The direction in which to play the word
- * @param letters 
- * @returns Whether the word could be validly played, which indices it was played on, the remaining letters, and the letter usage; or `null` if the play would be out-of-bounds
+ * Works out which tiles of the hand aren't on a solution's board, e.g. to show what's left over from a partial board
+ * @param solution The solution to check
+ * @returns Mapping of each uppercase Latin character to the number of that letter in the hand but not on the board
  */
-function play_word(word: Uint8Array, row_idx: number, col_idx: number, board: Board, direction: "horizontal"|"vertical", letters: Uint8Array): [boolean, Array<[number, number]>, Uint8Array, "Remaining"|"Overused"|"Finished"]|null {
-    const played_indices: Array<[number, number]> = [];
-    if (direction === "horizontal") {
-        if (col_idx + word.length >= BOARD_SIZE) {
-            return null;
-        }
-        const remaining_letters = Uint8Array.from(letters);
-        // Check if the word will start or end at a letter
-        let valid_loc = (col_idx != 0 && board.get_val(row_idx, col_idx-1) != EMPTY_VALUE) || (BOARD_SIZE-col_idx <= word.length && board.get_val(row_idx, col_idx+word.length) != EMPTY_VALUE);
-        // Check if the word will border any letters on the top or bottom
-        if (!valid_loc) {
-            for (let c_idx=col_idx; c_idx<col_idx+word.length; c_idx++) {
-                if ((row_idx < BOARD_SIZE-1 && board.get_val(row_idx+1, c_idx) != EMPTY_VALUE) || (row_idx > 0 && board.get_val(row_idx-1, c_idx) != EMPTY_VALUE)) {
-                    valid_loc = true;
-                    break;
-                }
-            }
-        }
-        if (!valid_loc) {
-            return [false, played_indices, remaining_letters, "Remaining"];
+export function unused_letters(solution: solution_t) {
+    const state = solution.state;
+    const on_board = count_board_letters(state.board, state.min_col, state.max_col, state.min_row, state.max_row);
+    const return_chars = new Map<string, number>();
+    [...UPPERCASE].forEach((c, i) => return_chars.set(c, Math.max(0, state.letters[i] - on_board[i])));
+    return return_chars;
+}
+
+/**
+ * Generates random letters based on user input
+ * @param what Whether to generate characters from an "infinite set" (i.e. all are equal likelihood), or selected from "standard Bananagrams" (144 tiles) or "double Bananagrams" (288 tiles)
+ * @param how_many How many tiles to randomly generate; must be greater than 0, and less than 144 for regular Bananagrams, or 288 for double
+ * @returns Mapping of each uppercase Latin character to the number of times it's present
+ */
+export async function get_random_letters(what: "infinite set"|"standard Bananagrams"|"double Bananagrams", how_many: number) {
+    return new Promise<Map<string, number>>((resolve, reject) => {
+        if (how_many < 1) {
+            reject("The number to choose should be greater than 0");
+            return;
         }
-        else {
-            let entirely_overlaps = true;
-            for (let i=0; i<word.length; i++) {
-                if (board.get_val(row_idx, col_idx+i) == EMPTY_VALUE) {
-                    board.set_val(row_idx, col_idx+i, word[i]);
-                    played_indices.push([row_idx, col_idx+i]);
-                    entirely_overlaps = false;
-                    if (remaining_letters[word[i]] === 0) {
-                        return [false, played_indices, remaining_letters, "Overused"];
-                    }
-                    remaining_letters[word[i]] -= 1;
+        const return_chars = new Map<string, number>();
+        [...UPPERCASE].forEach(c => {
+            return_chars.set(c, 0);
+        });
+        if (what == "infinite set") {
+            // For "infinite set", randomly generate characters
+            for (let i=0; i<how_many; i++) {
+                const random_num = getRandomInt(0, 25);
+                const random_char = String.fromCharCode(random_num+65);
+                if (return_chars.has(random_char)) {
+                    return_chars.set(random_char, return_chars.get(random_char)!+1);
                 }
-                else if (board.get_val(row_idx, col_idx+i) !== word[i]) {
-                    return [false, played_indices, remaining_letters, "Remaining"];
+                else {
+                    reject("Missing value in return dictionary: " + random_char);
+                    return;
                 }
             }
-            if (remaining_letters.every(count => count === 0) && !entirely_overlaps) {
-                return [true, played_indices, remaining_letters, "Finished"];
+        }
+        else if (what === "standard Bananagrams") {
+            if (how_many > 144) {
+                reject("The number to choose msut be less than 144 for standard Bananagrams");
+                return;
             }
-            else {
-                return [!entirely_overlaps, played_indices, remaining_letters, "Remaining"];
+            const to_choose_from = [];
+            for (let i=0; i<UPPERCASE.length; i++) {
+                for (let j=0; j<REGULAR_TILES[i]; j++) {
+                    to_choose_from.push(UPPERCASE.charAt(i));
+                }
             }
-        }
-    }
-    else {
-        if (row_idx + word.length >= BOARD_SIZE) {
-            return null;
-        }
-        const remaining_letters = Uint8Array.from(letters);
-        // Check if the word will start or end at a letter
-        let valid_loc = (row_idx != 0 && board.get_val(row_idx-1, col_idx) != EMPTY_VALUE) || (BOARD_SIZE-row_idx <= word.length && board.get_val(row_idx+word.length, col_idx) != EMPTY_VALUE);
-        // Check if the word will border any letters on the right or left
-        if (!valid_loc) {
-            for (let r_idx=row_idx; r_idx<row_idx+word.length; r_idx++) {
-                if ((col_idx < BOARD_SIZE-1 && board.get_val(r_idx, col_idx+1) != EMPTY_VALUE) || (col_idx > 0 && board.get_val(r_idx, col_idx-1) != EMPTY_VALUE)) {
-                    valid_loc = true;
-                    break;
+            for (let i=0; i<how_many; i++) {
+                const random_num = getRandomInt(0, to_choose_from.length-1);
+                const random_char = to_choose_from[random_num];
+                if (return_chars.has(random_char)) {
+                    return_chars.set(random_char, return_chars.get(random_char)!+1);
+                }
+                else {
+                    reject("Missing value in return dictionary: " + random_char);
+                    return;
                 }
+                to_choose_from.splice(random_num, 1);
             }
         }
-        if (!valid_loc) {
-            return [false, played_indices, remaining_letters, "Remaining"];
-        }
-        else {
-            let entirely_overlaps = true;
-            for (let i=0; i<word.length; i++) {
-                if (board.get_val(row_idx+i, col_idx) == EMPTY_VALUE) {
-                    board.set_val(row_idx+i, col_idx, word[i]);
-                    played_indices.push([row_idx+i, col_idx]);
-                    entirely_overlaps = false;
-                    if (remaining_letters[word[i]] === 0) {
-                        return [false, played_indices, remaining_letters, "Overused"];
-                    }
-                    remaining_letters[word[i]] -= 1;
-                }
-                else if (board.get_val(row_idx+i, col_idx) !== word[i]) {
-                    return [false, played_indices, remaining_letters, "Remaining"];
+        else if (what === "double Bananagrams") {
+            if (how_many > 288) {
+                reject("The number to choose msut be less than 288 for double Bananagrams");
+                return;
+            }
+            const to_choose_from = [];
+            for (let i=0; i<UPPERCASE.length; i++) {
+                for (let j=0; j<REGULAR_TILES[i]; j++) {
+                    to_choose_from.push(UPPERCASE.charAt(i));
                 }
             }
-            if (remaining_letters.every(count => count == 0) && !entirely_overlaps) {
-                return [true, played_indices, remaining_letters, "Finished"];
-            }
-            else {
-                return [!entirely_overlaps, played_indices, remaining_letters, "Remaining"];
+            for (let i=0; i<how_many; i++) {
+                const random_num = getRandomInt(0, to_choose_from.length-1);
+                const random_char = to_choose_from[random_num];
+                if (return_chars.has(random_char)) {
+                    return_chars.set(random_char, return_chars.get(random_char)!+1);
+                }
+                else {
+                    reject("Missing value in return dictionary: " + random_char);
+                    return;
+                }
+                to_choose_from.splice(random_num, 1);
             }
         }
-    }
-}
-
-/**
- * Undoes a play on the `board`
- * @param board `Board` being undone (is modified in-place)
- * @param played_indices Array of the indices in `board` that need to be reset
- */
-function undo_play(board: Board, played_indices: Array<[number, number]>) {
-    for (const index of played_indices) {
-        board.set_val(index[0], index[1], EMPTY_VALUE);
-    }
+        else {
+            reject("`what` must be \"infinite set\", \"standard Bananagrams\", or \"double Bananagrams\", not " + what);
+            return;
+        }
+        resolve(return_chars);
+    })
 }
 
 /**
- * Recursively solves Bananagrams
- * @param board The `Board` to modify in-place
+ * Builds the solution returned to the frontend
+ * @param board The solved `Board`
  * @param min_col Minimum occupied column index in `board`
  * @param max_col Maximum occupied column index in `board`
  * @param min_row Minimum occupied row index in `board`
  * @param max_row Maximum occupied row index in `board`
- * @param valid_words_vec Array of arrays, each representing a word (see `convert_word_to_array`)
- * @param valid_words_set Set of hashed word arrays
  * @param letters Length-26 array of the number of each letter in the hand
- * @param depth Depth of the current recursive call
- * @param play_sequence Sequence of played words for this current run
- * @param previous_play_sequence Sequence of played words for the previous run, if any
- * @returns Whether the word could be validly played, and the new minimum/maximum indices of the board, or `null` on out-of-bounds failure
+ * @param play_sequence Sequence of played words that made `board`
+ * @param previous_idxs Indices that were played on the previous board (see `get_previous_idxs`)
+ * @param start When solving started
+ * @param words_checked How many words were tried while solving
+ * @param existing_failure Why building off of the previous board failed, if it did
+ * @returns The solution
  */
-function play_further(board: Board, min_col: number, max_col: number, min_row: number, max_row: number, valid_words_vec: Array<Uint8Array>, valid_words_set: Set<number>, letters: Uint8Array, depth: number, play_sequence: PlaySequence, previous_play_sequence: PlaySequence): [boolean, number, number, number, number]|null {
-    if (depth+1 < previous_play_sequence.length) {
-        const word = previous_play_sequence[depth+1][0];
-        const row_idx = previous_play_sequence[depth+1][1][0];
-        const col_idx = previous_play_sequence[depth+1][1][1];
-        const res = play_word(word, row_idx, col_idx, board, previous_play_sequence[depth+1][1][2], letters);
-        if (res == null) {
-            return res;
-        }
-        if (res[0]) {
-            if (previous_play_sequence[depth+1][1][2] === "horizontal") {
-                const new_min_col = Math.min(min_col, col_idx);
-                const new_max_col = Math.max(max_col, col_idx+word.length);
-                const new_min_row = Math.min(min_row, row_idx);
-                const new_max_row = Math.max(max_row, row_idx);
-                if (is_board_valid_horizontal(board, new_min_col, new_max_col, new_min_row, new_max_row, row_idx, col_idx, col_idx+word.length-1, valid_words_set)) {
-                    // If it's valid, go to the next recursive level (where completion will be checked)
-                    play_sequence.push([word, [res[1][0][0], res[1][0][1], "horizontal"]]);
-                    if (res[3] === "Finished") {
-                        return [true, new_min_col, new_max_col, new_min_row, new_max_row];
-                    }
-                    else if (res[3] === "Remaining") {
-                        const res2 = play_further(board, new_min_col, new_max_col, new_min_row, new_max_row, valid_words_vec, valid_words_set, res[2], depth+1, play_sequence, previous_play_sequence);
-                        if (res2 == null) {
-                            return null;
-                        }
-                        else if (res2[0]) {
-                            // If that recursive stack finishes successfully, we're done! (could have used another Result or Option rather than a bool in the returned tuple, but oh well)
-                            return res2;
-                        }
-                        else {
-                            // Otherwise, undo the previous play (cloning the board before each play so we don't have to undo is *way* slower)
-                            play_sequence.pop();
-                            undo_play(board, res[1]);
-                        }
-                    }
-                }
-                else {
-                    // If the play formed some invalid words, undo the previous play
-                    undo_play(board, res[1]);
-                }
-            }
-            else {
-                const new_min_col = Math.min(min_col, col_idx);
-                const new_max_col = Math.max(max_col, col_idx);
-                const new_min_row = Math.min(min_row, row_idx);
-                const new_max_row = Math.max(max_row, row_idx+word.length);
-                if (is_board_valid_vertical(board, new_min_col, new_max_col, new_min_row, new_max_row, row_idx, row_idx+word.length-1, col_idx, valid_words_set)) {
-                    play_sequence.push([word, [res[1][0][0], res[1][0][1], "vertical"]]);
-                    if (res[3] === "Finished") {
-                        return [true, new_min_col, new_max_col, new_min_row, new_max_row];
-                    }
-                    else if (res[3] === "Remaining") {
-                        const res2 = play_further(board, new_min_col, new_max_col, new_min_row, new_max_row, valid_words_vec, valid_words_set, res[2], depth+1, play_sequence, previous_play_sequence);
-                        if (res2 == null) {
-                            return null;
-                        }
-                        else if (res2[0]) {
-                            return res2;
-                        }
-                        else {
-                            play_sequence.pop();
-                            undo_play(board, res[1]);
-                        }
-                    }
-                }
-                else {
-                    undo_play(board, res[1]);
-                }
-            }
-        }
-        else {
-            // If trying to play the board was invalid, undo the play
-            undo_play(board, res[1]);
+function build_solution(board: Board, min_col: number, max_col: number, min_row: number, max_row: number, letters: Uint8Array, play_sequence: PlaySequence|undefined, previous_idxs: Set<number>, start: Date, words_checked: number, existing_failure?: ExistingFailure): solution_t {
+    return {
+        board: board_to_vec(board, min_col, max_col, min_row, max_row, previous_idxs),
+        elapsed: ((new Date()).getMilliseconds() - start.getMilliseconds()),
+        quality: solution_score(board, min_col, max_col, min_row, max_row),
+        state: {
+            board: board.arr,
+            min_col: min_col,
+            max_col: max_col,
+            min_row: min_row,
+            max_row: max_row,
+            letters: letters,
+            // Dictionary words are views into one buffer holding the whole dictionary (see `convert_words_to_arena`), which would be copied along with them when the solution is posted
+            play_sequence: play_sequence?.map<PlaySequence[number]>(([word, position]) => [Uint8Array.from(word), position])
+        },
+        stats: {
+            words_checked: words_checked,
+            board_words_unique: all_words_unique_on_board(board, min_col, max_col, min_row, max_row),
+            compactness_optimization_rounds: 0,
+            score: board_score(board, min_col, max_col, min_row, max_row),
+            word_sort: "dictionary",
+            polish_iterations: 0,
+            polish_score_delta: 0,
+            topology: board_topology(board, min_col, max_col, min_row, max_row),
+            letter_balance: letter_balance(board, min_col, max_col, min_row, max_row),
+            candidate_cache_hit: false,
+            quadrant_balance_std_dev: quadrant_stats(board, min_col, max_col, min_row, max_row).std_dev,
+            pruned_subtrees: 0,
+            vowel_prunes: 0,
+            confidence: 1,
+            centroid: centroid(board, min_col, max_col, min_row, max_row),
+            used_removal_strategy: false
+        },
+        existing_failure: existing_failure
+    };
+}
+
+/**
+ * Builds the value returned when the solver runs out of words to check
+ * @param opening_index Index of the opening word that was being tried
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @param ctx Bookkeeping for the search that ran out
+ * @param resume Where the search was continued from, if it was
+ * @returns Where the search left off, so that it can be continued
+ */
+function search_timeout(opening_index: number, letters: Uint8Array, ctx: SearchContext, resume?: SearchState): SearchTimeout {
+    const words_checked = ctx.words_checked + (resume?.words_checked ?? 0);
+    return {
+        message: "No solution was found after checking " + words_checked + " words (the best partial board left " + ctx.fewest_remaining + " letters)",
+        search_state: {
+            opening_index: opening_index,
+            words_checked: words_checked,
+            best_leftover_letters: ctx.fewest_remaining,
+            letters: letters
         }
-        return [false, min_col, max_col, min_row, max_row];
+    };
+}
+
+/**
+ * Checks whether the whole search's budget has run out, as opposed to just the share for the current opening word
+ * @param ctx Bookkeeping for the search
+ * @param config Options for the solver
+ * @returns Whether `config.max_words_to_check` words have been tried
+ */
+function overall_budget_spent(ctx: SearchContext, config: SolverConfig) {
+    return config.max_words_to_check != null && ctx.words_checked > config.max_words_to_check;
+}
+
+/**
+ * How many letters of the opening word later words may play through, unless `SolverConfig.filter_letters_on_board` says otherwise
+ */
+const DEFAULT_FILTER_LETTERS_ON_BOARD = 1;
+/**
+ * Hands with more tiles than this are considered large by `resolve_strategy`
+ */
+const LARGE_HAND_SIZE = 30;
+/**
+ * Large hands with a smaller fraction of vowels than this are solved shortest words first by `resolve_strategy`
+ */
+const LOW_VOWEL_RATIO = 0.3;
+
+/**
+ * Picks the word order for the `"auto"` strategy: small hands solve fastest with long opening words, but large hands short on vowels
+ * usually need a lattice of short words, so those are tried shortest first
+ * @param config Options for the solver
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @returns `config` if its strategy isn't `"auto"` or it already has a word order, otherwise a copy of it with `word_sort` chosen
+ */
+function resolve_strategy(config: SolverConfig, letters: Uint8Array): SolverConfig {
+    if ((config.strategy ?? "auto") !== "auto" || config.word_sort != null) {
+        return config;
     }
-    // If we're at an odd depth, play horizontally first (trying to alternate horizontal-vertical-horizontal as a heuristic to solve faster)
-    else if (depth % 2 == 1) {
-        for (const word of  valid_words_vec) {
-            // Try across all rows (starting from one before to one after)
-            for (let row_idx=min_row-1; row_idx<max_row+2; row_idx++) {
-                // For each row, try across all columns (starting from the farthest out the word could be played)
-                for (let col_idx=min_col-word.length; col_idx<max_col+2; col_idx++) {
-                    // Using the ? because `play_word` can give an `Err` if the index is out of bounds
-                    const res = play_word(word, row_idx, col_idx, board, "horizontal", letters);
-                    if (res == null) {
-                        return null;
-                    }
-                    else if (res[0]) {
-                        // If the word was played successfully (i.e. it's not a complete overlap and it borders at least one existing tile), then check the validity of the new words it forms
-                        const new_min_col = Math.min(min_col, col_idx);
-                        const new_max_col = Math.max(max_col, col_idx+word.length);
-                        const new_min_row = Math.min(min_row, row_idx);
-                        const new_max_row = Math.max(max_row, row_idx);
-                        if (is_board_valid_horizontal(board, new_min_col, new_max_col, new_min_row, new_max_row, row_idx, col_idx, col_idx+word.length-1, valid_words_set)) {
-                            // If it's valid, go to the next recursive level (unless we've all the letters, at which point we're done)
-                            play_sequence.push([word, [res[1][0][0], res[1][0][1], "horizontal"]]);
-                            if (res[3] === "Finished") {
-                                return [true, new_min_col, new_max_col, new_min_row, new_max_row];
-                            }
-                            else if (res[3] === "Remaining") {
-                                const res2 = play_further(board, new_min_col, new_max_col, new_min_row, new_max_row, valid_words_vec, valid_words_set, res[2], depth+1, play_sequence, previous_play_sequence);
-                                if (res2 == null) {
-                                    return null;
-                                }
-                                else if (res2[0]) {
-                                    // If that recursive stack finishes successfully, we're done! (could have used another Result or Option rather than a bool in the returned tuple, but oh well)
-                                    return res2;
-                                }
-                                else {
-                                    // Otherwise, undo the previous play (cloning the board before each play so we don't have to undo is *way* slower)
-                                    play_sequence.pop();
-                                    undo_play(board, res[1]);
-                                }
-                            }
-                        }
-                        else {
-                            // If the play formed some invalid words, undo the previous play
-                            undo_play(board, res[1]);
-                        }
-                    }
-                    else {
-                        // If trying to play the board was invalid, undo the play
-                        undo_play(board, res[1]);
-                    }
-                }
-            }
-        }
-        // If trying every word horizontally didn't work, try vertically instead
-        for (const word of valid_words_vec) {
-            // Try down all columns
-            for (let col_idx=min_col-1; col_idx<max_col+2; col_idx++) {
-                // This is analgous to the above
-                for (let row_idx=min_row-word.length; row_idx<max_row+2; row_idx++) {
-                    const res = play_word(word, row_idx, col_idx, board, "vertical", letters);
-                    if (res == null) {
-                        return null;
-                    }
-                    else if (res[0]) {
-                        const new_min_col = Math.min(min_col, col_idx);
-                        const new_max_col = Math.max(max_col, col_idx);
-                        const new_min_row = Math.min(min_row, row_idx);
-                        const new_max_row = Math.max(max_row, row_idx+word.length);
-                        if (is_board_valid_vertical(board, new_min_col, new_max_col, new_min_row, new_max_row, row_idx, row_idx+word.length-1, col_idx, valid_words_set)) {
-                            play_sequence.push([word, [res[1][0][0], res[1][0][1], "vertical"]]);
-                            if (res[3] === "Finished") {
-                                return [true, new_min_col, new_max_col, new_min_row, new_max_row];
-                            }
-                            else if (res[3] === "Remaining") {
-                                const res2 = play_further(board, new_min_col, new_max_col, new_min_row, new_max_row, valid_words_vec, valid_words_set, res[2], depth+1, play_sequence, previous_play_sequence);
-                                if (res2 == null) {
-                                    return null;
-                                }
-                                else if (res2[0]) {
-                                    return res2;
-                                }
-                                else {
-                                    play_sequence.pop();
-                                    undo_play(board, res[1]);
-                                }
-                            }
-                        }
-                        else {
-                            undo_play(board, res[1]);
-                        }
-                    }
-                    else {
-                        undo_play(board, res[1]);
-                    }
-                }
-            }
-        }
-        return [false, min_col, max_col, min_row, max_row];
+    const hand_size = letters.reduce((a, b) => a + b, 0);
+    const vowels = VOWELS.reduce((a, vowel) => a + letters[vowel], 0);
+    const word_sort: word_sort_t = hand_size > LARGE_HAND_SIZE && vowels/hand_size < LOW_VOWEL_RATIO ? "length_asc" : "dictionary";
+    return {...config, word_sort: word_sort};
+}
+
+/**
+ * The words of the short dictionary that could be made from a hand
+ */
+export interface CandidateCache {
+    /**
+     * Length-26 array of the number of each letter in the hand
+     */
+    letters: Uint8Array,
+    /**
+     * The dictionary and the solver options the words were filtered with (see `candidate_key`); the cache is only used if these match
+     */
+    key: string,
+    /**
+     * Indices into `AppState.all_words_short` of the makeable words, in increasing order
+     */
+    indices: Uint32Array
+}
+
+/**
+ * Describes everything other than the hand that decides which words are makeable
+ * @param state Current state of the app
+ * @param config Options for the solver
+ * @returns A string that's the same whenever the same dictionary and filters are used
+ */
+export function candidate_key(state: AppState, config: SolverConfig) {
+    return dictionary_fingerprint(state.all_words_short) + ":" + (config.no_two_letter_words ? "no_two" : "") + ":" + forbidden_words(config).join(",");
+}
+
+/**
+ * Finds the words of the short dictionary that can be made from a hand, extending `state.candidate_cache` if the hand has only gained letters since it was built
+ * @param state Current state of the app
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @param config Options for the solver (uses `no_two_letter_words`)
+ * @returns Indices into `state.all_words_short` of the makeable words in increasing order, and whether `state.candidate_cache` was used
+ */
+export function makeable_word_indices(state: AppState, letters: Uint8Array, config: SolverConfig): [Uint32Array, boolean] {
+    const cached_letters = state.candidate_cache?.letters ?? new Uint8Array(26);
+    const cached_indices = state.candidate_cache?.indices ?? new Uint32Array(0);
+    const usable = state.candidate_cache?.key === candidate_key(state, config) && cached_letters.every((count, i) => count <= letters[i]);
+    if (usable && array_equal(cached_letters, letters)) {
+        return [cached_indices, true];
     }
-    // If we're at an even depth, play vertically first. Otherwise this is analgous to the above.
-    else {
-        for (const word of valid_words_vec) {
-            // Try down all columns
-            for (let col_idx=min_col-1; col_idx<max_col+2; col_idx++) {
-                for (let row_idx=min_row-word.length; row_idx<max_row+2; row_idx++) {
-                    const res = play_word(word, row_idx, col_idx, board, "vertical", letters);
-                    if (res == null) {
-                        return null;
-                    }
-                    else if (res[0]) {
-                        const new_min_col = Math.min(min_col, col_idx);
-                        const new_max_col = Math.max(max_col, col_idx);
-                        const new_min_row = Math.min(min_row, row_idx);
-                        const new_max_row = Math.max(max_row, row_idx+word.length);
-                        if (is_board_valid_vertical(board, new_min_col, new_max_col, new_min_row, new_max_row, row_idx, row_idx+word.length-1, col_idx, valid_words_set)) {
-                            play_sequence.push([word, [res[1][0][0], res[1][0][1], "vertical"]]);
-                            if (res[3] === "Finished") {
-                                return [true, new_min_col, new_max_col, new_min_row, new_max_row]; 
-                            }
-                            else if (res[3] === "Remaining") {
-                                const res2 = play_further(board, new_min_col, new_max_col, new_min_row, new_max_row, valid_words_vec, valid_words_set, res[2], depth+1, play_sequence, previous_play_sequence);
-                                if (res2 == null) {
-                                    return null;
-                                }
-                                else if (res2[0]) {
-                                    return res2;
-                                }
-                                else {
-                                    play_sequence.pop();
-                                    undo_play(board, res[1]);
-                                }
-                            }
-                        }
-                        else {
-                            undo_play(board, res[1]);
-                        }
-                    }
-                    else {
-                        undo_play(board, res[1]);
+    const dictionary = state.all_words_short;
+    const masks = dictionary_masks(dictionary);
+    const hand_mask = letter_mask(null, letters);
+    const is_candidate = (i: number) => (masks[i] & ~hand_mask) === 0 && is_makeable(dictionary[i], letters) && !(config.no_two_letter_words && dictionary[i].length === 2);
+    if (state.candidate_cache?.key === candidate_key(state, config) && letters.every((count, i) => count <= cached_letters[i])) {
+        // The hand has only lost letters (e.g. when solving part of it), so every makeable word is already cached
+        return [cached_indices.filter(is_candidate), true];
+    }
+    // Only build the index if it fits in memory (estimated generously, as if no word repeats a letter)
+    if (usable && (LETTER_INDICES.has(dictionary) || memory_available(config, 5*dictionary.reduce((a, word) => a + word.length, 0)))) {
+        // Every cached word is still makeable, since the hand has only gained letters; the only new ones need more of an added letter than the old hand had
+        const index = letter_index(dictionary);
+        const added = new Set<number>();
+        for (let letter=0; letter<26; letter++) {
+            if (letters[letter] > cached_letters[letter]) {
+                index.words[letter].forEach((i, j) => {
+                    if (index.counts[letter][j] > cached_letters[letter] && is_candidate(i)) {
+                        added.add(i);
                     }
-                }
+                });
             }
         }
-        for (const word of valid_words_vec) {
-            // Try across all rows
-            for (let row_idx=min_row-1; row_idx<max_row+2; row_idx++) {
-                for (let col_idx=min_col-word.length; col_idx<max_col+2; col_idx++) {
-                    const res = play_word(word, row_idx, col_idx, board, "horizontal", letters);
-                    if (res == null) {
-                        return null;
-                    }
-                    if (res[0]) {
-                        const new_min_col = Math.min(min_col, col_idx);
-                        const new_max_col = Math.max(max_col, col_idx+word.length);
-                        const new_min_row = Math.min(min_row, row_idx);
-                        const new_max_row = Math.max(max_row, row_idx);
-                        if (is_board_valid_horizontal(board, new_min_col, new_max_col, new_min_row, new_max_row, row_idx, col_idx, col_idx+word.length-1, valid_words_set)) {
-                            play_sequence.push([word, [res[1][0][0], res[1][0][1], "horizontal"]]);
-                            if (res[3] === "Finished") {
-                                return [true, new_min_col, new_max_col, new_min_row, new_max_row];
-                            }
-                            else if (res[3] === "Remaining") {
-                                const res2 = play_further(board, new_min_col, new_max_col, new_min_row, new_max_row, valid_words_vec, valid_words_set, res[2], depth+1, play_sequence, previous_play_sequence);
-                                if (res2 == null) {
-                                    return null;
-                                }
-                                else if (res2[0]) {
-                                    return res2;
-                                }
-                                else {
-                                    play_sequence.pop();
-                                    undo_play(board, res[1]);
-                                }
-                            }
-                        }
-                        else {
-                            undo_play(board, res[1]);
-                        }
-                    }
-                    else {
-                        undo_play(board, res[1]);
-                    }
-                }
-            }
+        const indices = new Uint32Array(cached_indices.length + added.size);
+        indices.set(cached_indices);
+        indices.set(Array.from(added), cached_indices.length);
+        return [indices.sort(), true];
+    }
+    // Without the index (or the cache), check every word
+    const indices: number[] = [];
+    for (let i=0; i<dictionary.length; i++) {
+        if (is_candidate(i)) {
+            indices.push(i);
         }
-        return [false, min_col, max_col, min_row, max_row];
     }
+    return [Uint32Array.from(indices), false];
 }
 
 /**
- * Tries to play a single letter on the board
- * @param board 
- * @param min_col The `Board` on which to try to play the `letter`
- * @param max_col Minimum occupied column index in `board`
- * @param min_row Maximum occupied column index in `board`
- * @param max_row Minimum occupied row index in `board`
- * @param letter Maximum occupied row index in `board`
- * @param valid_words_set Set of all valid hashed words
- * @returns Either `null` if no solution was found, or `(row, col, new_min_col, new_max_col, new_min_row, new_max_row)` on success
+ * Gets the words of the short dictionary that can be made from a hand, in the order the solver should try them
+ * @param state Current state of the app
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @param config Options for the solver (uses `word_sort`, `avoid_repeats_from_history`, and `word_priorities`)
+ * @returns The makeable words
  */
-function play_one_letter(board: Board, min_col: number, max_col: number, min_row: number, max_row: number, letter: number, valid_words_set: Set<number>): [number, number, number, number, number, number]|null {
-    // Loop through all possible locations and check if the letter works there
-    for (let row=min_row-1; row<max_row+2; row++) {
-        for (let col=min_col-1; col<max_col+2; col++) {
-            if (row < BOARD_SIZE && col < BOARD_SIZE && board.get_val(row, col) == EMPTY_VALUE) {   // row/col don't need to be checked if they're greater than 0 since they'd underflow
-                if ((col > 0 && board.get_val(row, col-1) != EMPTY_VALUE) || (col < BOARD_SIZE-1 && board.get_val(row, col+1) != EMPTY_VALUE) || (row > 0 && board.get_val(row-1, col) != EMPTY_VALUE) || (row < BOARD_SIZE-1 && board.get_val(row+1, col) != EMPTY_VALUE)) {
-                    board.set_val(row, col, letter);
-                    const new_min_col = Math.min(min_col, col);
-                    const new_max_col = Math.max(max_col, col);
-                    const new_min_row = Math.min(min_row, row);
-                    const new_max_row = Math.max(max_row, row);
-                    // Could also use `is_board_valid_vertical`
-                    if (is_board_valid_horizontal(board, new_min_col, new_max_col, new_min_row, new_max_row, row, col, col, valid_words_set)) {
-                        // If it's valid, return the (potentially) new bounds, along with the location the letter was played
-                        return [row, col, new_min_col, new_max_col, new_min_row, new_max_row];
-                    }
-                    else {
-                        // If the board wasn't ok, reset this spot
-                        board.set_val(row, col, EMPTY_VALUE);
-                    }
-                }
+function makeable_words(state: AppState, letters: Uint8Array, config: SolverConfig) {
+    const valid_words_vec = Array.from(makeable_word_indices(state, letters, config)[0], i => state.all_words_short[i]);
+    if (config.word_sort === "length_asc") {
+        // Stable, so words of the same length stay in dictionary order
+        valid_words_vec.sort((a, b) => a.length - b.length);
+    }
+    if (config.avoid_repeats_from_history && state.word_history != null && state.word_history.length > 0) {
+        // Stable, so the words used before stay in order after every other word
+        const history = new Set(state.word_history);
+        valid_words_vec.sort((a, b) => Number(history.has(convert_array_to_word(a))) - Number(history.has(convert_array_to_word(b))));
+    }
+    if (config.word_priorities != null && config.word_priorities.length > 0) {
+        const by_word = new Map(valid_words_vec.map(word => [convert_array_to_word(word), word]));
+        const prioritized = new Set<Uint8Array>();
+        for (const word of config.word_priorities) {
+            const makeable = by_word.get(word.toUpperCase());
+            if (makeable != null) {
+                prioritized.add(makeable);
             }
         }
+        return Array.from(prioritized).concat(valid_words_vec.filter(word => !prioritized.has(word)));
     }
-    // Return `null` if we don't find a solution
-    return null;
+    return valid_words_vec;
 }
 
-/// Attempts to play off an existing board
-/// # Arguments
-/// * `previous_play_sequence` - Sequence of previous played moves
-/// * `valid_words_vec` - Vector of valid words for the given hand of letters
-/// * `valid_words_set` - HashSet of valid words (HashSet of `valid_words_vec` for faster membership checking)
-/// * `letters` - Array of the number of each letter in the hand
-/// # Returns
-/// `Option` with:
-/// * `Board` - updated board
-/// * `PlaySequence` - updated play sequence
-/// * `usize` - Minimum occupied column index in `board`
-/// * `usize` - Maximum occupied column index in `board`
-/// * `usize` - Minimum occupied row index in `board`
-/// * `usize` - Maximum occupied row index in `board`
-/// 
-/// *or `None` if no valid play can be made on the existing board*
-function play_existing(previous_play_sequence: PlaySequence, valid_words_vec: Array<Uint8Array>, valid_words_set: Set<number>, letters: Uint8Array): [Board, PlaySequence, number, number, number, number]|null {
-    const board = new Board();
-    const row = previous_play_sequence[0][1][0];
-    const col_start = previous_play_sequence[0][1][1];
-    const word = previous_play_sequence[0][0];
-    const use_letters = Uint8Array.from(letters);
-    const word_letters = new Set<number>();
-    for (let i=0; i<word.length; i++) {
-        board.set_val(row, col_start+i, word[i]);
-        use_letters[word[i]] -= 1;
-        word_letters.add(word[i]);
+/**
+ * Solves a hand from scratch, trying each makeable word in turn as the opening word
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @param state Current state of the app
+ * @param config Options for the solver
+ * @param start When solving started
+ * @param existing_failure Why building off of the previous board failed, if it was tried
+ * @param resume Where a previous search of the same hand left off, if continuing it
+ * @param ctx Bookkeeping for the search, if shared with other searches (e.g. so that they share a budget)
+ * @returns The solution, a `SearchTimeout` if the budget ran out, or an error message if no solution exists
+ */
+function play_from_scratch(letters: Uint8Array, state: AppState, config: SolverConfig, start: Date, existing_failure?: ExistingFailure, resume?: SearchState, ctx = new_search_context(letters, config)): solution_t|SearchTimeout|string {
+    const valid_words_vec = makeable_words(state, letters, config);
+    if (valid_words_vec.length == 0) {
+        return "No valid words can be formed from the current letters - dump and try again!";
     }
-    const min_col = col_start;
-    const min_row = row;
-    const max_col = col_start + (word.length-1);
-    const max_row = row;
-    const play_sequence: PlaySequence = [];
-    play_sequence.push([word, [row, col_start, "horizontal"]]);
-    if (use_letters.every(count => count == 0)) {
-        return [board, play_sequence, min_col, max_col, min_row, max_row];
+    const showcase = config.strategy === "longest_word";
+    // Whether each opening word only gets a share of the budget
+    const per_opening_budget = showcase || config.max_words_per_opening != null;
+    if (showcase) {
+        // Stable, so words of the same length stay in order
+        valid_words_vec.sort((a, b) => b.length - a.length);
     }
-    else {
-        const new_valid_words_vec = valid_words_vec.filter(word => check_filter_after_play(use_letters, word, word_letters));
-        const res = play_further(board, min_col, max_col, min_row, max_row, new_valid_words_vec, valid_words_set, use_letters, 0, play_sequence, previous_play_sequence);
-        if (res == null) {
-            return null;
+    if (config.locked_word != null) {
+        // Open with the locked word so that it's kept if at all possible
+        const locked = convert_word_to_array(config.locked_word.toUpperCase());
+        const locked_index = valid_words_vec.findIndex(word => array_equal(word, locked));
+        if (locked_index > 0) {
+            valid_words_vec.unshift(...valid_words_vec.splice(locked_index, 1));
+        }
+    }
+    if (resume != null) {
+        ctx.fewest_remaining = resume.best_leftover_letters;
+    }
+    // The furthest board reached from each opening word, if requested
+    const opening_boards: OpeningBoard[] = [];
+    MEMORY_USAGE.opening_board_bytes = 0;
+    // Loop through each word and play it on a new board
+    for (let opening_index=resume?.opening_index ?? 0; opening_index<valid_words_vec.length; opening_index++) {
+        const word = valid_words_vec[opening_index];
+        if (per_opening_budget) {
+            // Give each opening word its own share of the budget
+            const share = Math.min(config.max_words_per_opening ?? Infinity, showcase ? config.anchor_budget ?? Infinity : Infinity);
+            ctx.config = {...config, max_words_to_check: Math.min(ctx.words_checked + share, config.max_words_to_check ?? Infinity)};
+            ctx.exhausted = false;
+        }
+        // A word that led nowhere from another opening word may still fit on this one
+        ctx.backtracks = [];
+        const board = new Board();
+        const col_start = Math.round(board_center() - word.length/2);
+        const row = board_center();
+        const use_letters = Uint8Array.from(letters);
+        for (let i=0; i<word.length; i++) {
+            board.set_val(row, col_start+i, word[i]);
+            use_letters[word[i]] -= 1;
+        }
+        const min_col = col_start;
+        const min_row = row;
+        const max_col = col_start + (word.length-1);
+        const max_row = row;
+        const play_sequence: PlaySequence = [];
+        play_sequence.push([word, [row, col_start, "horizontal"]]);
+        if (!check_budget(ctx)) {
+            if (per_opening_budget && !overall_budget_spent(ctx, config)) {
+                continue;
+            }
+            flush_best_board(ctx);
+            return search_timeout(opening_index, letters, ctx, resume);
+        }
+        // Stop collecting opening boards once memory runs low, counting each cell as a reference to a string
+        const board_bytes = 8*(max_col - min_col + 1)*(max_row - min_row + 1);
+        const collect = config.collect_opening_boards && memory_available(config, board_bytes);
+        if (collect) {
+            MEMORY_USAGE.opening_board_bytes += board_bytes;
+        }
+        ctx.opening_best = collect ? {word: convert_array_to_word(word), board: board_to_vec(board, min_col, max_col, min_row, max_row, new Set()), leftover_letters: use_letters.reduce((a, b) => a + b, 0)} : undefined;
+        if (use_letters.every(count => count == 0)) {
+            if (!is_acceptable_solution(board, min_col, max_col, min_row, max_row, config)) {
+                if (ctx.opening_best != null) {
+                    opening_boards.push(ctx.opening_best);
+                }
+                continue;
+            }
+            const previous_idxs = get_previous_idxs(state.last_game?.play_sequence, play_sequence);
+            const solution = build_solution(board, min_col, max_col, min_row, max_row, letters, play_sequence, previous_idxs, start, ctx.words_checked, existing_failure);
+            if (ctx.opening_best != null) {
+                solution.opening_boards = [...opening_boards, ctx.opening_best];
+            }
+            if (showcase) {
+                solution.showcased_word = convert_array_to_word(word);
+            }
+            return solution;
         }
         else {
-            if (res[0]) {
-                return [board, play_sequence, res[1], res[2], res[3], res[4]];
+            // Reduce the set of remaining words to check to those that can be played with the letters not in the first word (plus a few of the tiles played in the first word)
+            const word_letters = new Set(word);
+            const new_valid_words_vec = valid_words_vec.filter(word => check_filter_after_play(use_letters, word, word_letters, ctx.config.filter_letters_on_board ?? DEFAULT_FILTER_LETTERS_ON_BOARD));
+            const valid_words_set = state.all_words_short_set;
+            // Begin the recursive processing
+            const result = play_further(board, min_col, max_col, min_row, max_row, new_valid_words_vec, valid_words_set, use_letters, 0, play_sequence, [], ctx);
+            if (ctx.opening_best != null) {
+                opening_boards.push(ctx.opening_best);
+            }
+            if (result == null && ctx.exhausted) {
+                if (per_opening_budget && !overall_budget_spent(ctx, config)) {
+                    // Only this opening word's share ran out, so move on to the next one
+                    continue;
+                }
+                flush_best_board(ctx);
+                return search_timeout(opening_index, letters, ctx, resume);
+            }
+            else if (result == null || !result[0]) {
+                // A different opening word can still lead to a solution (e.g. when this one's boards were all rejected by the
+                // solver's options), so only give up once every opening word has been tried
+                continue;
             }
             else {
-                return null;
+                let [, new_min_col, new_max_col, new_min_row, new_max_row] = result;
+                let rounds = 0;
+                if (config.optimize_compactness) {
+                    [rounds, new_min_col, new_max_col, new_min_row, new_max_row] = hill_climb(board, new_min_col, new_max_col, new_min_row, new_max_row, play_sequence, valid_words_set, config, board_spread, Infinity);
+                }
+                let polish_iterations = 0;
+                let polish_score_delta = 0;
+                if (config.polish) {
+                    const ranking = config.ranking ?? DENSITY_RANKING;
+                    const polish_cost: board_cost_t = (b, ...bounds) => -combined_score(solution_score(b, ...bounds), ranking);
+                    const before = polish_cost(board, new_min_col, new_max_col, new_min_row, new_max_row);
+                    [polish_iterations, new_min_col, new_max_col, new_min_row, new_max_row] = hill_climb(board, new_min_col, new_max_col, new_min_row, new_max_row, play_sequence, valid_words_set, config, polish_cost, config.polish_rounds ?? POLISH_ROUNDS);
+                    polish_score_delta = before - polish_cost(board, new_min_col, new_max_col, new_min_row, new_max_row);
+                }
+                const previous_idxs = get_previous_idxs(state.last_game?.play_sequence, play_sequence);
+                const solution = build_solution(board, new_min_col, new_max_col, new_min_row, new_max_row, letters, play_sequence, previous_idxs, start, ctx.words_checked, existing_failure);
+                solution.stats.compactness_optimization_rounds = rounds;
+                solution.stats.polish_iterations = polish_iterations;
+                solution.stats.polish_score_delta = polish_score_delta;
+                solution.stats.pruned_subtrees = ctx.pruned;
+                solution.stats.vowel_prunes = ctx.vowel_prunes;
+                solution.stats.used_removal_strategy = config.optimize_compactness === true || config.polish === true;
+                if (config.collect_opening_boards) {
+                    solution.opening_boards = opening_boards;
+                }
+                if (showcase) {
+                    solution.showcased_word = convert_array_to_word(word);
+                }
+                return solution;
             }
         }
     }
+    return no_solution_message(config, ctx.reached_edge);
 }
 
 /**
- * Async function to get the playable words for a given hand of letters
- * @param available_letters Mapping string letters to numeric quanity of each letter
- * @param state Current state of the app
- * @returns Object with two keys - "short" (common words playable using `available_letters`) and "long" (Scrabble words playable using `available_letters`)
+ * Start of the message returned when no board was found and the board reached the edge of the board array, so it couldn't grow any further that way
  */
-export async function get_playable_words(available_letters: Map<string, number>, state: AppState) {
-    return new Promise<{short: string[], long: string[]}>((resolve, reject) => {
-        // Check if we have all the letters from the frontend
-        const letters = new Uint8Array(26);
-        for (const c of UPPERCASE) {
-            const num = available_letters.get(c);
-            if (num != null) {
-                if (num < 0) {
-                    reject("Number of letter " + c + " is " + num + ", but must be greater than or equal to 0!");
-                    return;
-                }
-                letters[c.charCodeAt(0) - 65] = num;
-            }
-            else {
-                reject("Missing letter: " + c);
-                return;
+export const BOARD_TOO_LARGE = "No board was found that fits in the " + BOARD_SIZE + "x" + BOARD_SIZE + " grid";
+
+/**
+ * Builds the message returned when every opening word has been tried without finding a solution, pointing out the options that may be to blame
+ * @param config Options for the solver
+ * @param reached_edge Whether any placement was skipped because the board already reached the edge of the board array (see `SearchContext.reached_edge`)
+ * @returns The error message; if the board reached the edge, it starts with `BOARD_TOO_LARGE` so that callers can tell it apart
+ */
+function no_solution_message(config: SolverConfig, reached_edge: boolean) {
+    const relaxable: string[] = [];
+    if (config.min_intersections_per_word != null && config.intersection_mode === "hard") {
+        relaxable.push("lowering min_intersections_per_word or making it soft");
+    }
+    if (config.min_intersection_density != null && config.min_intersection_density > 0) {
+        relaxable.push("lowering min_intersection_density");
+    }
+    if (config.no_two_letter_words) {
+        relaxable.push("allowing two-letter words (no_two_letter_words)");
+    }
+    if (config.no_duplicate_words) {
+        relaxable.push("allowing repeated words (no_duplicate_words)");
+    }
+    if (config.min_width != null || config.min_height != null) {
+        relaxable.push("lowering min_width and min_height");
+    }
+    if (config.max_area != null) {
+        relaxable.push("raising max_area");
+    }
+    if (config.max_word_count != null) {
+        relaxable.push("raising max_word_count");
+    }
+    if (config.family_friendly) {
+        relaxable.push("allowing every word (family_friendly)");
+    }
+    if (config.forbidden_words != null && config.forbidden_words.length > 0) {
+        relaxable.push("forbidding fewer words (forbidden_words)");
+    }
+    if (config.required_letters != null && config.required_letters.length > 0) {
+        relaxable.push("requiring fewer letters (required_letters)");
+    }
+    if (config.strict) {
+        relaxable.push("allowing letters that aren't part of a word (strict)");
+    }
+    if (config.strict_adjacency) {
+        relaxable.push("allowing words to touch diagonally (strict_adjacency)");
+    }
+    if (reached_edge) {
+        // The options may still be to blame too, since the search carries on with the placements that fit
+        relaxable.unshift("making the board more compact (e.g. with prefer_square)");
+        return BOARD_TOO_LARGE + " - try " + relaxable.join(", ") + ", or dump";
+    }
+    if (relaxable.length > 0) {
+        return "No board was found that meets the solver's options - try " + relaxable.join(", ") + ", or dump";
+    }
+    return "No valid words can be formed from the current letters - dump and try again!";
+}
+
+/**
+ * Number of tiles added in each chunk when solving in chunks (see `SolverConfig.chunked`)
+ */
+const CHUNK_SIZE = 20;
+
+/**
+ * Orders the tiles of a hand so that every prefix of the order has about the same mix of letters as the whole hand
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @returns Every tile of the hand (from 0 for 'A' to 25 for 'Z')
+ */
+function proportional_tile_order(letters: Uint8Array) {
+    const total = letters.reduce((a, b) => a + b, 0);
+    const taken = new Uint8Array(26);
+    const order: number[] = [];
+    for (let k=1; k<=total; k++) {
+        // Take the letter that's furthest behind its share of the first `k` tiles
+        let best = -1;
+        let best_deficit = -Infinity;
+        for (let i=0; i<26; i++) {
+            const deficit = letters[i]*k/total - taken[i];
+            if (taken[i] < letters[i] && deficit > best_deficit) {
+                best = i;
+                best_deficit = deficit;
             }
         }
-        const playable_short = state.all_words_short.filter(word => is_makeable(word, letters)).map(convert_array_to_word);
-        const playable_long = state.all_words_long.filter(word => is_makeable(word, letters)).map(convert_array_to_word);
-        resolve({short: playable_short, long: playable_long});
-    });
+        taken[best] += 1;
+        order.push(best);
+    }
+    return order;
 }
 
 /**
- * Generates random letters based on user input
- * @param what Whether to generate characters from an "infinite set" (i.e. all are equal likelihood), or selected from "standard Bananagrams" (144 tiles) or "double Bananagrams" (288 tiles)
- * @param how_many How many tiles to randomly generate; must be greater than 0, and less than 144 for regular Bananagrams, or 288 for double
- * @returns Mapping of each uppercase Latin character to the number of times it's present
+ * Solves a large hand a chunk at a time: the first chunk is solved from scratch, and each following chunk is added by building off of the previous board
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @param state Current state of the app
+ * @param config Options for the solver
+ * @param start When solving started
+ * @param existing_failure Why building off of the previous board failed, if it was tried
+ * @param on_best_board Called with the best board reached so far, if `config.stream_best_boards` is set
+ * @returns The solution, or `null` if the hand is too small to chunk or a chunk couldn't be added
  */
-export async function get_random_letters(what: "infinite set"|"standard Bananagrams"|"double Bananagrams", how_many: number) {
-    return new Promise<Map<string, number>>((resolve, reject) => {
-        if (how_many < 1) {
-            reject("The number to choose should be greater than 0");
-            return;
+function play_chunked(letters: Uint8Array, state: AppState, config: SolverConfig, start: Date, existing_failure?: ExistingFailure, on_best_board?: (best_board: BestBoard) => void) {
+    const order = proportional_tile_order(letters);
+    if (order.length <= CHUNK_SIZE) {
+        return null;
+    }
+    const chunk = new Uint8Array(26);
+    for (const tile of order.slice(0, CHUNK_SIZE)) {
+        chunk[tile] += 1;
+    }
+    const first = play_from_scratch(chunk, state, config, start, undefined, undefined, new_search_context(chunk, config, on_best_board));
+    if (typeof first === "string" || !("board" in first)) {
+        return null;
+    }
+    let play_sequence = first.state.play_sequence!;
+    let words_checked = first.stats.words_checked;
+    for (let size=CHUNK_SIZE; size<order.length; size+=CHUNK_SIZE) {
+        for (const tile of order.slice(size, size+CHUNK_SIZE)) {
+            chunk[tile] += 1;
         }
-        const return_chars = new Map<string, number>();
-        [...UPPERCASE].forEach(c => {
-            return_chars.set(c, 0);
-        });
-        if (what == "infinite set") {
-            // For "infinite set", randomly generate characters
-            for (let i=0; i<how_many; i++) {
-                const random_num = getRandomInt(0, 25);
-                const random_char = String.fromCharCode(random_num+65);
-                if (return_chars.has(random_char)) {
-                    return_chars.set(random_char, return_chars.get(random_char)!+1);
-                }
-                else {
-                    reject("Missing value in return dictionary: " + random_char);
-                    return;
-                }
-            }
+        const ctx = new_search_context(chunk, config, on_best_board);
+        const attempt = play_existing(play_sequence, makeable_words(state, chunk, config), state.all_words_short_set, chunk, ctx);
+        words_checked += ctx.words_checked;
+        if (attempt == null) {
+            return null;
         }
-        else if (what === "standard Bananagrams") {
-            if (how_many > 144) {
-                reject("The number to choose msut be less than 144 for standard Bananagrams");
-                return;
-            }
-            const to_choose_from = [];
-            for (let i=0; i<UPPERCASE.length; i++) {
-                for (let j=0; j<REGULAR_TILES[i]; j++) {
-                    to_choose_from.push(UPPERCASE.charAt(i));
-                }
-            }
-            for (let i=0; i<how_many; i++) {
-                const random_num = getRandomInt(0, to_choose_from.length-1);
-                const random_char = to_choose_from[random_num];
-                if (return_chars.has(random_char)) {
-                    return_chars.set(random_char, return_chars.get(random_char)!+1);
-                }
-                else {
-                    reject("Missing value in return dictionary: " + random_char);
-                    return;
-                }
-                to_choose_from.splice(random_num, 1);
-            }
+        if (size+CHUNK_SIZE >= order.length) {
+            const previous_idxs = get_previous_idxs(state.last_game?.play_sequence, attempt[1]);
+            return build_solution(attempt[0], attempt[2], attempt[3], attempt[4], attempt[5], letters, attempt[1], previous_idxs, start, words_checked, existing_failure);
         }
-        else if (what === "double Bananagrams") {
-            if (how_many > 288) {
-                reject("The number to choose msut be less than 288 for double Bananagrams");
-                return;
+        play_sequence = attempt[1];
+    }
+    return null;
+}
+
+/**
+ * Solves a hand where only some letters must be played (see `SolverConfig.required_letters`), playing as many of the others as possible
+ *
+ * Parts of the hand that include every required letter are solved from scratch, largest first, until one succeeds or the budget runs out.
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @param required Length-26 array of the number of each letter that must be played
+ * @param state Current state of the app
+ * @param config Options for the solver
+ * @param start When solving started
+ * @param on_best_board Called with the best board reached so far, if `config.stream_best_boards` is set
+ * @returns The solution (with the whole hand as its letters, so that the tiles left out can be found), or an error message if no part of the hand could be solved
+ */
+function play_required(letters: Uint8Array, required: Uint8Array, state: AppState, config: SolverConfig, start: Date, on_best_board?: (best_board: BestBoard) => void): solution_t|string {
+    const optional = letters.map((count, i) => count - required[i]);
+    // Every part of the hand shares the budget
+    const ctx = new_search_context(letters, config, on_best_board);
+    for (let count=optional.reduce((a, b) => a + b, 0); count>=0; count--) {
+        for (const part of sub_hands(optional, count)) {
+            // Each part tried counts against the budget, so that parts with no makeable words still use it up
+            if (!check_budget(ctx)) {
+                return no_solution_message(config, ctx.reached_edge);
             }
-            const to_choose_from = [];
-            for (let i=0; i<UPPERCASE.length; i++) {
-                for (let j=0; j<REGULAR_TILES[i]; j++) {
-                    to_choose_from.push(UPPERCASE.charAt(i));
-                }
+            const hand = part.map((n, i) => n + required[i]);
+            ctx.fewest_remaining = hand.reduce((a, b) => a + b, 0);
+            const attempt = play_from_scratch(hand, state, config, start, undefined, undefined, ctx);
+            if (typeof attempt === "string") {
+                continue;
             }
-            for (let i=0; i<how_many; i++) {
-                const random_num = getRandomInt(0, to_choose_from.length-1);
-                const random_char = to_choose_from[random_num];
-                if (return_chars.has(random_char)) {
-                    return_chars.set(random_char, return_chars.get(random_char)!+1);
-                }
-                else {
-                    reject("Missing value in return dictionary: " + random_char);
-                    return;
-                }
-                to_choose_from.splice(random_num, 1);
+            else if (!("board" in attempt)) {
+                // The budget ran out partway through this part
+                return no_solution_message(config, ctx.reached_edge);
             }
+            attempt.state.letters = letters;
+            return attempt;
         }
-        else {
-            reject("`what` must be \"infinite set\", \"standard Bananagrams\", or \"double Bananagrams\", not " + what);
-            return;
-        }
-        resolve(return_chars);
-    })
+    }
+    return no_solution_message(config, ctx.reached_edge);
 }
 
 /**
  * Async function to solve a Bananagrams board
  * @param available_letters Mapping of string letters to numeric quantity of each letter
  * @param state Current state of the app
+ * @param config Options for the solver
+ * @param resume Where a previous search of the same hand from scratch left off, if continuing it
+ * @param on_best_board Called with the best board found so far as the search goes, at most once every `STREAM_INTERVAL_MS`, if `config.stream_best_boards` is set
  */
-function play_bananagrams(available_letters: Map<string, number>, state: AppState) {
+export function play_bananagrams(available_letters: Map<string, number>, state: AppState, config: SolverConfig, resume?: SearchState, on_best_board?: (best_board: BestBoard) => void) {
     const start = new Date();
     // Check if we have all the letters from the frontend
-    const letters = new Uint8Array(26);
-    for (const c of UPPERCASE) {
-        const num = available_letters.get(c);
-        if (num != null) {
-            if (num < 0) {
-                return "Number of letter " + c + " is " + num + ", but must be greater than or equal to 0!";
-            }
-            letters[c.charCodeAt(0) - 65] = num;
+    const letters = letters_from_map(available_letters);
+    if (typeof letters === "string") {
+        return letters;
+    }
+    if (resume != null && !array_equal(resume.letters, letters)) {
+        return "The search being continued was for a different hand";
+    }
+    const required = required_letter_counts(config);
+    if (required.some((count, i) => letters[i] < count)) {
+        return "The hand doesn't have all of the required letters";
+    }
+    const resolved = resolve_strategy(config, letters);
+    free_memory_if_needed(resolved);
+    const forbidden = forbidden_words(config);
+    const solve_state = forbidden.length > 0 ? state_without_words(state, forbidden) : state;
+    const [indices, cache_hit] = makeable_word_indices(solve_state, letters, resolved);
+    const candidate_cache: CandidateCache = {letters: letters, key: candidate_key(solve_state, resolved), indices: indices};
+    // Only some of the hand has to be played if letters are required
+    const result = required.some(count => count > 0) ? play_required(letters, required, {...solve_state, candidate_cache: candidate_cache}, resolved, start, on_best_board) : solve_letters(letters, {...solve_state, candidate_cache: candidate_cache}, resolved, start, resume, on_best_board);
+    if (typeof result !== "string" && "board" in result) {
+        result.candidate_cache = candidate_cache;
+        result.stats.candidate_cache_hit = cache_hit;
+        result.stats.word_sort = resolved.word_sort ?? "dictionary";
+        result.stats.confidence = 1 - Math.min(1, result.stats.words_checked/(resolved.max_words_to_check ?? Infinity));
+        if (config.avoid_repeats_from_history) {
+            const history = new Set(state.word_history ?? []);
+            const board_words = extract_placed_words(board_from_array(result.state.board), result.state.min_col, result.state.max_col, result.state.min_row, result.state.max_row);
+            result.reused_history_words = Array.from(new Set(board_words.map(placed => placed.word).filter(word => history.has(word))));
         }
-        else {
-            return "Missing letter: " + c;
+        const opening = result.state.play_sequence?.[0];
+        if (config.relative_to_opening && opening != null) {
+            const [row, col] = opening[1];
+            result.opening_origin = [row, col];
+            result.relative_bounds = {min_col: result.state.min_col - col, max_col: result.state.max_col - col, min_row: result.state.min_row - row, max_row: result.state.max_row - row};
+        }
+        if (config.locked_word != null) {
+            result.locked_word_kept = locked_word_cells(board_from_array(result.state.board), result.state.min_col, result.state.max_col, result.state.min_row, result.state.max_row, config.locked_word).size > 0;
         }
     }
-    if (state.last_game != null) {
+    return result;
+}
+
+/**
+ * Gets every word the solver's options keep off of the board
+ * @param config Options for the solver
+ * @returns The uppercase words from `config.forbidden_words`, along with `OFFENSIVE_WORDS` if `config.family_friendly` is set
+ */
+function forbidden_words(config: SolverConfig) {
+    const words = (config.forbidden_words ?? []).map(word => word.toUpperCase());
+    return config.family_friendly ? words.concat(OFFENSIVE_WORDS) : words;
+}
+
+/**
+ * Removes words from the dictionary used by the solver, so that they're neither played nor allowed as crossing words
+ * @param state Current state of the app; this is not modified
+ * @param words Uppercase words to remove
+ * @returns A copy of `state` without `words` in `all_words_short` or `all_words_short_set`
+ */
+function state_without_words(state: AppState, words: string[]): AppState {
+    const removed = new Set(words);
+    const all_words_short_set = new Set(state.all_words_short_set);
+    words.forEach(word => all_words_short_set.delete(vec_hasher(convert_word_to_array(word))));
+    return {...state, all_words_short: state.all_words_short.filter(word => !removed.has(convert_array_to_word(word))), all_words_short_set: all_words_short_set};
+}
+
+/**
+ * Solves a hand, building off of the previous board if possible
+ * @param letters Length-26 array of the number of each letter in the hand
+ * @param state Current state of the app
+ * @param config Options for the solver (with the strategy resolved; see `resolve_strategy`)
+ * @param start When solving started
+ * @param resume Where a previous search of the same hand from scratch left off, if continuing it
+ * @param on_best_board Called with the best board reached so far, if `config.stream_best_boards` is set
+ * @returns The solution, a `SearchTimeout` if the budget ran out, or an error message if no solution exists
+ */
+function solve_letters(letters: Uint8Array, state: AppState, config: SolverConfig, start: Date, resume?: SearchState, on_best_board?: (best_board: BestBoard) => void): solution_t|SearchTimeout|string {
+    // Filled in if building off of the previous board fails
+    let existing_failure: ExistingFailure|undefined = undefined;
+    // A board that only played part of its hand (see `SolverConfig.required_letters`) can't be built off of
+    const last_complete = state.last_game != null && array_equal(count_board_letters(state.last_game.board, state.last_game.min_col, state.last_game.max_col, state.last_game.min_row, state.last_game.max_row), state.last_game.letters);
+    if (state.last_game != null && last_complete && resume == null) {
         let comparison: comparison_t = "Same";
         let seen_greater = EMPTY_VALUE;
         for (let i=0; i<26; i++) {
@@ -1056,166 +6172,169 @@ function play_bananagrams(available_letters: Map<string, number>, state: AppStat
         if (comparison === "Same") {
             const board = new Board();
             board.arr = state.last_game.board;
-            return {
-                board: board_to_vec(board, state.last_game.min_col, state.last_game.max_col, state.last_game.min_row, state.last_game.max_row, new Set()),
-                elapsed: ((new Date()).getMilliseconds() - start.getMilliseconds()),
-                state: {
-                    board: state.last_game.board,
-                    min_col: state.last_game.min_col,
-                    max_col: state.last_game.max_col,
-                    min_row: state.last_game.min_row,
-                    max_row: state.last_game.max_row,
-                    letters: state.last_game.letters
-                }
+            if (is_acceptable_reused_board(board, state.last_game.min_col, state.last_game.max_col, state.last_game.min_row, state.last_game.max_row, config)) {
+                return build_solution(board, state.last_game.min_col, state.last_game.max_col, state.last_game.min_row, state.last_game.max_row, state.last_game.letters, state.last_game.play_sequence, new Set(), start, 0);
+            }
+            // Otherwise the options have changed since the previous board was solved, so continue with the code that starts from scratch
+            existing_failure = {
+                comparison: comparison,
+                single_letter_attempted: false,
+                words_checked: 0,
+                best_leftover_letters: 0
             };
         }
         else if (comparison === "GreaterByOne") {
-            const valid_words_vec = state.all_words_short.filter(word => is_makeable(word, letters));
-            const valid_words_set = new Set(valid_words_vec.map(vec_hasher));
+            const valid_words_vec = makeable_words(state, letters, config);
+            const valid_words_set = state.all_words_short_set;
             const board = new Board();
             board.arr = state.last_game.board;
-            const res = play_one_letter(board, state.last_game.min_col, state.last_game.max_col, state.last_game.min_row, state.last_game.max_row, seen_greater, valid_words_set);
-            if (res == null) {
-                // If we failed when playing one letter, try playing off the existing board
-                const attempt = play_existing(state.last_game.play_sequence!, valid_words_vec, valid_words_set, letters);
-                if (attempt == null) {
-                    // If we failed, continue with the code that starts from scratch
-                }
-                else {
-                    const previous_idxs = get_previous_idxs(state.last_game.play_sequence, attempt[1]);
-                    return {
-                        board: board_to_vec(attempt[0], attempt[2], attempt[3], attempt[4], attempt[5], previous_idxs),
-                        elapsed: ((new Date()).getMilliseconds() - start.getMilliseconds()),
-                        state: {
-                            board: attempt[0].arr,
-                            min_col: attempt[2],
-                            max_col: attempt[3],
-                            min_row: attempt[4],
-                            max_row: attempt[5],
-                            letters: letters,
-                            play_sequence: attempt[1]
-                        }
-                    };
-                }
-            }
-            else {
+            const res = play_one_letter(board, state.last_game.min_col, state.last_game.max_col, state.last_game.min_row, state.last_game.max_row, seen_greater, valid_words_set, config.no_two_letter_words ?? false);
+            if (res != null && is_acceptable_reused_board(board, res[2], res[3], res[4], res[5], config)) {
                 const play_sequence: PlaySequence = [...state.last_game.play_sequence!];
                 const arr = new Uint8Array(1);
                 arr[0] = seen_greater;
                 play_sequence.push([arr, [res[0], res[1], "horizontal"]]);
                 const previous_idxs = get_previous_idxs(state.last_game.play_sequence, play_sequence);
-                return {
-                    board: board_to_vec(board, res[2], res[3], res[4], res[5], previous_idxs),
-                    elapsed: ((new Date()).getMilliseconds() - start.getMilliseconds()),
-                    state: {
-                        board: board.arr,
-                        min_col: res[2],
-                        max_col: res[3],
-                        min_row: res[4],
-                        max_row: res[5],
-                        letters: letters,
-                        play_sequence: play_sequence
-                    }
+                return build_solution(board, res[2], res[3], res[4], res[5], letters, play_sequence, previous_idxs, start, 0);
+            }
+            if (res != null) {
+                // Take the letter back off, so that the previous board is left as it was
+                board.set_val(res[0], res[1], EMPTY_VALUE);
+            }
+            // If we failed when playing one letter, try playing off the existing board
+            const ctx = new_search_context(letters, config, on_best_board);
+            const attempt = play_existing(state.last_game.play_sequence!, valid_words_vec, valid_words_set, letters, ctx);
+            if (attempt == null) {
+                // If we failed, continue with the code that starts from scratch
+                existing_failure = {
+                    comparison: comparison,
+                    single_letter_attempted: true,
+                    words_checked: ctx.words_checked,
+                    best_leftover_letters: ctx.fewest_remaining
                 };
             }
+            else {
+                const previous_idxs = get_previous_idxs(state.last_game.play_sequence, attempt[1]);
+                return build_solution(attempt[0], attempt[2], attempt[3], attempt[4], attempt[5], letters, attempt[1], previous_idxs, start, ctx.words_checked);
+            }
         }
         else if (comparison === "GreaterByMoreThanOne") {
             // If a letter has increased by more than one, or multiple have increased by one or more, then try playing off the existing board
-            const valid_words_vec = state.all_words_short.filter(word => is_makeable(word, letters));
-            const valid_words_set = new Set(valid_words_vec.map(vec_hasher));
-            const attempt = play_existing(state.last_game.play_sequence!, valid_words_vec, valid_words_set, letters);
+            const valid_words_vec = makeable_words(state, letters, config);
+            const valid_words_set = state.all_words_short_set;
+            const ctx = new_search_context(letters, config, on_best_board);
+            const attempt = play_existing(state.last_game.play_sequence!, valid_words_vec, valid_words_set, letters, ctx);
             if (attempt == null) {
                 // If we failed, continue with the code that starts from scratch
+                existing_failure = {
+                    comparison: comparison,
+                    single_letter_attempted: false,
+                    words_checked: ctx.words_checked,
+                    best_leftover_letters: ctx.fewest_remaining
+                };
             }
             else {
                 const previous_idxs = get_previous_idxs(state.last_game.play_sequence, attempt[1]);
-                return {
-                    board: board_to_vec(attempt[0], attempt[2], attempt[3], attempt[4], attempt[5], previous_idxs),
-                    elapsed: ((new Date()).getMilliseconds() - start.getMilliseconds()),
-                    state: {
-                        board: attempt[0].arr,
-                        min_col: attempt[2],
-                        max_col: attempt[3],
-                        min_row: attempt[4],
-                        max_row: attempt[5],
-                        letters: letters,
-                        play_sequence: attempt[1]
-                    }
-                };
+                return build_solution(attempt[0], attempt[2], attempt[3], attempt[4], attempt[5], letters, attempt[1], previous_idxs, start, ctx.words_checked);
             }
         }
         else {
             // We just want to continue to the code that starts from scratch
         }
     }
-    // Play from scratch
-    let valid_words_vec: Uint8Array[] = state.all_words_short.filter(word => is_makeable(word, letters));
-    if (valid_words_vec.length == 0) {
-        return "No valid words can be formed from the current letters - dump and try again!";
+    if (config.chunked && resume == null) {
+        const chunked = play_chunked(letters, state, config, start, existing_failure, on_best_board);
+        if (chunked != null) {
+            return chunked;
+        }
     }
-    // Loop through each word and play it on a new board
-    for (const word of valid_words_vec) {
-        const board = new Board();
-        const col_start = Math.round(BOARD_SIZE/2 - word.length/2);
-        const row = Math.round(BOARD_SIZE/2);
-        const use_letters = Uint8Array.from(letters);
-        for (let i=0; i<word.length; i++) {
-            board.set_val(row, col_start+i, word[i]);
-            use_letters[word[i]] -= 1;
+    if (config.avoid_repeats_from_history && state.word_history != null && state.word_history.length > 0 && resume == null) {
+        // First try without the words used before at all; if that doesn't find a solution (including running out of budget), they're allowed again as a last resort
+        const history = new Set(state.word_history);
+        const without_history = play_from_scratch(letters, {...state, all_words_short: state.all_words_short.filter(word => !history.has(convert_array_to_word(word)))}, config, start, existing_failure, undefined, new_search_context(letters, config, on_best_board));
+        if (typeof without_history !== "string" && "board" in without_history) {
+            return without_history;
         }
-        const min_col = col_start;
-        const min_row = row;
-        const max_col = col_start + (word.length-1);
-        const max_row = row;
-        const play_sequence: PlaySequence = [];
-        play_sequence.push([word, [row, col_start, "horizontal"]]);
-        if (use_letters.every(count => count == 0)) {
-            const previous_idxs = get_previous_idxs(state.last_game?.play_sequence, play_sequence);
-            return {
-                board: board_to_vec(board, min_col, max_col, min_row, max_row, previous_idxs),
-                elapsed: ((new Date()).getMilliseconds() - start.getMilliseconds()),
-                state: {
-                    board: board.arr,
-                    min_col: min_col,
-                    max_col: max_col,
-                    min_row: min_row,
-                    max_row: max_row,
-                    letters: letters,
-                    play_sequence: play_sequence
-                }
-            };
+    }
+    // Play from scratch
+    return play_from_scratch(letters, state, config, start, existing_failure, resume, new_search_context(letters, config, on_best_board));
+}
+
+/**
+ * Gets the large arrays in a result posted by the worker, so that they can be transferred out of it rather than copied
+ * 
+ * Once transferred, the arrays are empty in the worker, so the result mustn't be used there afterwards.
+ * @param result The result to be posted
+ * @returns The distinct buffers backing the board and the cached candidate words
+ */
+function result_transferables(result: ReturnType<typeof play_bananagrams>|ReturnType<typeof run_task>) {
+    const buffers = new Set<ArrayBuffer>();
+    if (typeof result !== "string" && "board" in result) {
+        buffers.add(result.state.board.buffer as ArrayBuffer);
+        if (result.candidate_cache != null) {
+            buffers.add(result.candidate_cache.indices.buffer as ArrayBuffer);
         }
-        else {
-            // Reduce the set of remaining words to check to those that can be played with the letters not in the first word (plus only one of the tiles played in the first word)
-            const word_letters = new Set(letters);
-            const new_valid_words_vec = valid_words_vec.filter(word => check_filter_after_play(use_letters, word, word_letters));
-            const valid_words_set = new Set(valid_words_vec.map(vec_hasher));
-            // Begin the recursive processing
-            const result = play_further(board, min_col, max_col, min_row, max_row, new_valid_words_vec, valid_words_set, use_letters, 0, play_sequence, []);
-            if (result == null || !result[0]) {
-                return "No valid words can be formed from the current letters - dump and try again!";
-            }
-            else {
-                const previous_idxs = get_previous_idxs(state.last_game?.play_sequence, play_sequence);
-                return {
-                    board: board_to_vec(board, result[1], result[2], result[3], result[4], previous_idxs),
-                    elapsed: ((new Date()).getMilliseconds() - start.getMilliseconds()),
-                    state: {
-                        board: board.arr,
-                        min_col: result[1],
-                        max_col: result[2],
-                        min_row: result[3],
-                        max_row: result[4],
-                        letters: letters,
-                        play_sequence: play_sequence
-                    }
-                };
-            }
+    }
+    return Array.from(buffers);
+}
+
+/**
+ * Describes the inputs of a solve for an error report, without including the inputs themselves
+ * @param available_letters Mapping of string letters to numeric quantity of each letter
+ * @param state Current state of the app
+ * @returns The number of each letter in the hand and the number of tiles on the previous board
+ */
+function describe_inputs(available_letters: Map<string, number>, state: AppState) {
+    const hand = [...UPPERCASE].map(c => c + "=" + (available_letters?.get(c) ?? "?")).join(" ");
+    const last_game = state?.last_game;
+    const tiles = last_game == null ? 0 : count_board_letters(last_game.board, last_game.min_col, last_game.max_col, last_game.min_row, last_game.max_row).reduce((a, b) => a + b, 0);
+    return "hand: " + hand + "; tiles on the previous board: " + tiles;
+}
+
+/**
+ * A slow function posted to the worker, so that it doesn't block the page (see `solver_tasks.ts`)
+ */
+export type WorkerTask = {task: "smallest_solvable_subset", args: Parameters<typeof find_smallest_solvable_subset>}|{task: "generate_solvable_hand", args: Parameters<typeof find_solvable_hand>};
+
+/**
+ * Runs a task posted to the worker
+ * @param task The task and its arguments
+ * @returns The task's result, or an error message
+ */
+function run_task(task: WorkerTask) {
+    if (task.task === "smallest_solvable_subset") {
+        return find_smallest_solvable_subset(...task.args);
+    }
+    else {
+        return find_solvable_hand(...task.args);
+    }
+}
+
+/**
+ * Runs whatever was posted to the worker, turning any error thrown into a failure message
+ * @param data The posted message: either a `WorkerTask`, or a hand to solve with its `letters`, `gameState`, `config` and `resume`
+ * @param post_message Posts a message back from the worker while the task is still running (used for `{best_board: BestBoard}`)
+ * @returns The result to post back
+ */
+export function handle_worker_message(data: any, post_message: (message: any) => void) {
+    try {
+        // Messages without a task are hands to solve
+        return data.task != null ? run_task(data as WorkerTask) : play_bananagrams(data.letters, data.gameState, data.config ?? {}, data.resume, best_board => post_message({best_board: best_board}));
+    }
+    catch (error) {
+        // Report the bug like any other failure rather than leaving the frontend waiting forever
+        let inputs: string;
+        try {
+            inputs = describe_inputs(data.letters, data.gameState);
+        }
+        catch {
+            inputs = "inputs couldn't be read";
         }
+        return "The solver hit an unexpected error: " + (error instanceof Error ? error.stack ?? error.message : String(error)) + " (" + inputs + ")";
     }
 }
 
 self.addEventListener("message", e => {
-    const result = play_bananagrams(e.data.letters, e.data.gameState);
-    self.postMessage(result);
+    const result = handle_worker_message(e.data, message => self.postMessage(message));
+    self.postMessage(result, {transfer: result_transferables(result)});
 }, false)
@@ -0,0 +1,57 @@
+import { AppState, GeneratedHand, SubsetResult, WorkerTask } from "./solver";
+
+/**
+ * Runs a slow solver function in a new worker, so that it doesn't block the page
+ * @param task The function to run and its arguments
+ * @returns `Promise` resolving to the function's result, or rejecting with its error message
+ */
+function run_in_worker<T>(task: WorkerTask) {
+    return new Promise<T>((resolve, reject) => {
+        const worker = new Worker(new URL("solver", import.meta.url), {type: "module"});
+        worker.addEventListener("message", e => {
+            worker.terminate();
+            if (typeof e.data === "string") {
+                reject(e.data);
+            }
+            else {
+                resolve(e.data as T);
+            }
+        });
+        // Errors the solver doesn't catch itself, e.g. if the worker fails to load
+        worker.addEventListener("error", e => {
+            worker.terminate();
+            reject("The solver stopped unexpectedly: " + e.message);
+        });
+        worker.postMessage(task);
+    });
+}
+
+/**
+ * Async function to find the fewest tiles of a hand that can be solved on their own, e.g. to know how much of the hand is needed to play right now
+ *
+ * Runs in a worker; see `find_smallest_solvable_subset` in `solver.ts` for how the search works.
+ * @param available_letters Mapping string letters to numeric quanity of each letter
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether to solve with the full Scrabble dictionary rather than just the common words
+ * @param max_words_to_check Maximum number of words to try when solving each part of the hand
+ * @returns The smallest solvable part of the hand found, along with a solution and the rest of the hand
+ */
+export async function smallest_solvable_subset(available_letters: Map<string, number>, state: AppState, use_long_dictionary: boolean, max_words_to_check: number) {
+    return run_in_worker<SubsetResult>({task: "smallest_solvable_subset", args: [available_letters, state, use_long_dictionary, max_words_to_check]});
+}
+
+/**
+ * Async function to deal a hand from a standard Bananagrams bag that the solver can solve, e.g. for making puzzles
+ *
+ * Runs in a worker (see `find_solvable_hand` in `solver.ts`).
+ * @param size How many tiles to deal; must be between 1 and 144
+ * @param seed Seed for dealing, so that the same seed always gives the same hand
+ * @param state Current state of the app
+ * @param use_long_dictionary Whether to solve with the full Scrabble dictionary rather than just the common words
+ * @param max_words_to_check Maximum number of words to try when solving each dealt hand
+ * @param max_attempts Maximum number of hands to deal before giving up
+ * @returns The first solvable hand dealt, along with a solution
+ */
+export async function generate_solvable_hand(size: number, seed: number, state: AppState, use_long_dictionary: boolean, max_words_to_check: number, max_attempts = 100) {
+    return run_in_worker<GeneratedHand>({task: "generate_solvable_hand", args: [size, seed, state, use_long_dictionary, max_words_to_check, max_attempts]});
+}
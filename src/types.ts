@@ -1,4 +1,4 @@
-import { GameState } from "./solver";
+import { CandidateCache, ExistingFailure, GameState, SolutionScore, SolverStats } from "./solver";
 
 /**
  * Type of the return after a solution is found
@@ -12,10 +12,26 @@ export type result_t = {
      * The time the function took to run
      */
     elapsed: number,
+    /**
+     * Measures of the quality of the solution
+     */
+    quality: SolutionScore,
     /**
      * The game state
      */
-    state: GameState
+    state: GameState,
+    /**
+     * Statistics about the search and the solution
+     */
+    stats: SolverStats,
+    /**
+     * If building off of the previous board was tried but failed, why it failed
+     */
+    existing_failure?: ExistingFailure,
+    /**
+     * The makeable words for the hand, to reuse in the next solve
+     */
+    candidate_cache?: CandidateCache
 };
 
 /**
@@ -21,7 +21,24 @@ export function writeText(data: string) {
  * Using Math.round() will give you a non-uniform distribution!
  * @param min Minimum value to generate
  * @param max Maximum value to generate
+ * @param random Source of random numbers in [0, 1); defaults to `Math.random`
  */
-export function getRandomInt(min: number, max: number) {
-    return Math.floor(Math.random() * (max - min + 1)) + min;
+export function getRandomInt(min: number, max: number, random: () => number = Math.random) {
+    return Math.floor(random() * (max - min + 1)) + min;
+}
+
+/**
+ * Creates a seeded source of random numbers (mulberry32), for when the same sequence must be reproducible
+ * @param seed Seed for the sequence
+ * @returns Function returning the next random number in [0, 1) on each call
+ */
+export function seededRandom(seed: number) {
+    let state = seed >>> 0;
+    return () => {
+        state = (state + 0x6D2B79F5) >>> 0;
+        let t = state;
+        t = Math.imul(t ^ (t >>> 15), t | 1);
+        t ^= t + Math.imul(t ^ (t >>> 7), t | 61);
+        return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+    };
 }
\ No newline at end of file
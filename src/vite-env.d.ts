@@ -0,0 +1,12 @@
+/// <reference types="vite/client" />
+
+interface ImportMetaEnv {
+    /**
+     * Which dictionaries to build the app with: `"short"` for only the common words, `"full"` for only the full Scrabble dictionary, or both if not set
+     */
+    readonly VITE_DICTIONARIES?: "short"|"full"
+}
+
+interface ImportMeta {
+    readonly env: ImportMetaEnv
+}